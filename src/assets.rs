@@ -0,0 +1,209 @@
+use amethyst::{
+    assets::{AssetStorage, Handle, Loader, ProgressCounter},
+    audio::{FlacFormat, Mp3Format, OggFormat, SourceHandle, WavFormat},
+    config::Config,
+    ecs::World,
+    prelude::WorldExt,
+    renderer::{ImageFormat, SpriteSheet, SpriteSheetFormat, Texture},
+    ui::{FontHandle, TtfFormat},
+};
+use amethyst_gltf::{GltfSceneAsset, GltfSceneFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::save::data_path;
+
+/// Assets sans lesquels le jeu ne peut pas demarrer, (categorie, nom)
+/// Les scenes sont celles nommees par `levels.ron`, les sons sont les seuls
+/// avertissements du joueur
+const REQUIRED: [(&str, &str); 7] = [
+    ("sounds", "coming"),
+    ("sounds", "screamer"),
+    ("sounds", "boot"),
+    ("fonts", "hud"),
+    ("textures", "bashar"),
+    ("textures", "vignette"),
+    ("sprite_sheets", "afit"),
+];
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum AudioFormat {
+    Mp3,
+    Wav,
+    Ogg,
+    Flac,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SoundEntry {
+    pub path: String,
+    pub format: AudioFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpriteSheetEntry {
+    pub texture: String,
+    /// Fichier RON decrivant les sprites de la texture
+    pub sheet: String,
+}
+
+/// Contenu de `assets/manifest.ron`, les chemins sont relatifs au dossier `assets`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetManifest {
+    pub scenes: HashMap<String, String>,
+    pub sounds: HashMap<String, SoundEntry>,
+    pub fonts: HashMap<String, String>,
+    pub textures: HashMap<String, String>,
+    pub sprite_sheets: HashMap<String, SpriteSheetEntry>,
+}
+
+impl AssetManifest {
+    /// Noms des assets requis absents du manifeste
+    pub fn missing(&self) -> Vec<String> {
+        REQUIRED
+            .iter()
+            .filter(|(category, name)| {
+                let name = &name.to_string();
+                match *category {
+                    "scenes" => !self.scenes.contains_key(name),
+                    "sounds" => !self.sounds.contains_key(name),
+                    "fonts" => !self.fonts.contains_key(name),
+                    "textures" => !self.textures.contains_key(name),
+                    "sprite_sheets" => !self.sprite_sheets.contains_key(name),
+                    _ => false,
+                }
+            })
+            .map(|(category, name)| format!("{}.{}", category, name))
+            .collect()
+    }
+
+    /// Lance le chargement de tous les assets du manifeste
+    pub fn load_assets(&self, world: &World, progress: &mut ProgressCounter) -> GameAssets {
+        let loader = world.read_resource::<Loader>();
+
+        let scenes = self
+            .scenes
+            .iter()
+            .map(|(name, path)| {
+                let scene = loader.load(
+                    path.as_str(),
+                    GltfSceneFormat::default(),
+                    &mut *progress,
+                    &world.read_resource(),
+                );
+                (name.clone(), scene)
+            })
+            .collect();
+
+        let sounds = self
+            .sounds
+            .iter()
+            .map(|(name, entry)| {
+                let storage = world.read_resource();
+                let path = entry.path.as_str();
+                let sound = match entry.format {
+                    AudioFormat::Mp3 => loader.load(path, Mp3Format, &mut *progress, &storage),
+                    AudioFormat::Wav => loader.load(path, WavFormat, &mut *progress, &storage),
+                    AudioFormat::Ogg => loader.load(path, OggFormat, &mut *progress, &storage),
+                    AudioFormat::Flac => loader.load(path, FlacFormat, &mut *progress, &storage),
+                };
+                (name.clone(), sound)
+            })
+            .collect();
+
+        let fonts = self
+            .fonts
+            .iter()
+            .map(|(name, path)| {
+                let font = loader.load(
+                    path.as_str(),
+                    TtfFormat,
+                    &mut *progress,
+                    &world.read_resource(),
+                );
+                (name.clone(), font)
+            })
+            .collect();
+
+        let texture_storage = world.read_resource::<AssetStorage<Texture>>();
+        let textures = self
+            .textures
+            .iter()
+            .map(|(name, path)| {
+                let texture = loader.load(
+                    path.as_str(),
+                    ImageFormat::default(),
+                    &mut *progress,
+                    &texture_storage,
+                );
+                (name.clone(), texture)
+            })
+            .collect();
+
+        let sprite_sheets = self
+            .sprite_sheets
+            .iter()
+            .map(|(name, entry)| {
+                let texture = loader.load(
+                    entry.texture.as_str(),
+                    ImageFormat::default(),
+                    &mut *progress,
+                    &texture_storage,
+                );
+                let sprite_sheet = loader.load(
+                    entry.sheet.as_str(),
+                    SpriteSheetFormat(texture),
+                    &mut *progress,
+                    &world.read_resource::<AssetStorage<SpriteSheet>>(),
+                );
+                (name.clone(), sprite_sheet)
+            })
+            .collect();
+
+        GameAssets {
+            scenes,
+            sounds,
+            fonts,
+            textures,
+            sprite_sheets,
+        }
+    }
+}
+
+pub fn load_manifest() -> Result<AssetManifest, String> {
+    let path = data_path("assets/manifest.ron").ok_or("Dossier du jeu introuvable")?;
+    AssetManifest::load(&path).map_err(|e| format!("{} : {}", path.display(), e))
+}
+
+/// Handles de tous les assets du manifeste, par nom
+#[derive(Default)]
+pub struct GameAssets {
+    scenes: HashMap<String, Handle<GltfSceneAsset>>,
+    sounds: HashMap<String, SourceHandle>,
+    fonts: HashMap<String, FontHandle>,
+    textures: HashMap<String, Handle<Texture>>,
+    sprite_sheets: HashMap<String, Handle<SpriteSheet>>,
+}
+
+impl GameAssets {
+    pub fn scene(&self, name: &str) -> Option<Handle<GltfSceneAsset>> {
+        self.scenes.get(name).cloned()
+    }
+
+    pub fn sound(&self, name: &str) -> Option<SourceHandle> {
+        self.sounds.get(name).cloned()
+    }
+
+    pub fn font(&self, name: &str) -> Option<FontHandle> {
+        self.fonts.get(name).cloned()
+    }
+
+    pub fn texture(&self, name: &str) -> Option<Handle<Texture>> {
+        self.textures.get(name).cloned()
+    }
+
+    pub fn sprite_sheet(&self, name: &str) -> Option<Handle<SpriteSheet>> {
+        self.sprite_sheets.get(name).cloned()
+    }
+}
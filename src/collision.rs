@@ -0,0 +1,264 @@
+use amethyst::core::math::Vector2;
+
+use crate::space::{is_in_zone, Zone};
+
+pub const PLAYER_RADIUS: f32 = 0.1;
+
+// Marge gardee entre le joueur et les murs apres un contact
+const SKIN: f32 = 1.0e-3;
+// Nombre de glissements maximum par deplacement
+const MAX_SLIDES: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub a: Vector2<f32>,
+    pub b: Vector2<f32>,
+}
+
+/// Murs du niveau, calcules a partir du contour des zones accessibles
+#[derive(Debug, Default)]
+pub struct Walls {
+    pub segments: Vec<Segment>,
+    pub zones: Vec<Zone>,
+}
+
+impl Walls {
+    /// Garde uniquement les morceaux de bords des zones qui ne donnent pas sur une autre zone
+    pub fn from_zones(zones: &[Zone]) -> Self {
+        let mut segments = Vec::new();
+
+        for zone in zones {
+            let (min_x, min_z, max_x, max_z) = *zone;
+
+            // (x fixe, bord en z, cote exterieur)
+            for &(x, outside) in &[(min_x, -1.0), (max_x, 1.0)] {
+                for (z0, z1) in uncovered(zones, min_z, max_z, |other| {
+                    let probe = x + outside * SKIN;
+                    if probe > other.0 && probe < other.2 {
+                        Some((other.1, other.3))
+                    } else {
+                        None
+                    }
+                }) {
+                    segments.push(Segment {
+                        a: Vector2::new(x, z0),
+                        b: Vector2::new(x, z1),
+                    });
+                }
+            }
+
+            // (z fixe, bord en x, cote exterieur)
+            for &(z, outside) in &[(min_z, -1.0), (max_z, 1.0)] {
+                for (x0, x1) in uncovered(zones, min_x, max_x, |other| {
+                    let probe = z + outside * SKIN;
+                    if probe > other.1 && probe < other.3 {
+                        Some((other.0, other.2))
+                    } else {
+                        None
+                    }
+                }) {
+                    segments.push(Segment {
+                        a: Vector2::new(x0, z),
+                        b: Vector2::new(x1, z),
+                    });
+                }
+            }
+        }
+
+        Walls {
+            segments,
+            zones: zones.to_vec(),
+        }
+    }
+
+    /// Deplace un cercle de rayon `radius` de `delta` en glissant le long des murs
+    pub fn slide(&self, from: Vector2<f32>, delta: Vector2<f32>, radius: f32) -> Vector2<f32> {
+        let mut pos = from;
+        let mut remaining = delta;
+
+        for _ in 0..MAX_SLIDES {
+            if remaining.norm_squared() < 1.0e-10 {
+                break;
+            }
+
+            let hit = self
+                .segments
+                .iter()
+                .filter_map(|segment| sweep(pos, remaining, radius, segment))
+                .min_by(|(t1, _), (t2, _)| t1.partial_cmp(t2).unwrap());
+
+            match hit {
+                Some((t, normal)) => {
+                    pos += remaining * t + normal * SKIN;
+                    remaining *= 1.0 - t;
+                    remaining -= normal * remaining.dot(&normal);
+                }
+                None => {
+                    pos += remaining;
+                    break;
+                }
+            }
+        }
+
+        // Au cas ou les erreurs d'arrondi nous feraient sortir du niveau
+        if self.zones.iter().any(|zone| is_in_zone(zone, pos.x, pos.y)) {
+            pos
+        } else {
+            from
+        }
+    }
+}
+
+/// Parties de [min, max] qui ne sont couvertes par aucune des zones
+fn uncovered<F>(zones: &[Zone], min: f32, max: f32, covering: F) -> Vec<(f32, f32)>
+where
+    F: Fn(&Zone) -> Option<(f32, f32)>,
+{
+    let mut covered: Vec<(f32, f32)> = zones.iter().filter_map(covering).collect();
+    covered.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let mut parts = Vec::new();
+    let mut start = min;
+    for (from, to) in covered {
+        if from > start {
+            parts.push((start, from.min(max)));
+        }
+        start = start.max(to);
+        if start >= max {
+            break;
+        }
+    }
+    if start < max {
+        parts.push((start, max));
+    }
+
+    parts.retain(|(from, to)| to - from > SKIN);
+    parts
+}
+
+/// Premier contact (fraction de `delta`, normale) d'un cercle qui se deplace vers un segment
+fn sweep(
+    pos: Vector2<f32>,
+    delta: Vector2<f32>,
+    radius: f32,
+    segment: &Segment,
+) -> Option<(f32, Vector2<f32>)> {
+    let edge = segment.b - segment.a;
+    let length = edge.norm();
+    let tangent = edge / length;
+
+    // Contact avec le segment lui meme, decale de `radius`
+    let mut normal = Vector2::new(-tangent.y, tangent.x);
+    let mut distance = (pos - segment.a).dot(&normal);
+    if distance < 0.0 {
+        normal = -normal;
+        distance = -distance;
+    }
+
+    let speed = delta.dot(&normal);
+    let mut best: Option<(f32, Vector2<f32>)> = None;
+    if speed < 0.0 {
+        let t = ((radius - distance) / speed).max(0.0);
+        if t <= 1.0 {
+            let along = (pos + delta * t - segment.a).dot(&tangent);
+            if along >= 0.0 && along <= length {
+                best = Some((t, normal));
+            }
+        }
+    }
+
+    // Contact avec les extremites du segment
+    for end in &[segment.a, segment.b] {
+        let offset = pos - end;
+        let a = delta.norm_squared();
+        let b = offset.dot(&delta);
+        let c = offset.norm_squared() - radius * radius;
+        if b >= 0.0 {
+            continue;
+        }
+
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            continue;
+        }
+
+        let t = ((-b - discriminant.sqrt()) / a).max(0.0);
+        if t <= 1.0 && !matches!(best, Some((best_t, _)) if best_t <= t) {
+            let contact = pos + delta * t - end;
+            best = Some((t, contact / contact.norm().max(1.0e-6)));
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOM: Zone = (0.0, 0.0, 2.0, 2.0);
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn large_delta_does_not_tunnel() {
+        let walls = Walls::from_zones(&[ROOM]);
+
+        let pos = walls.slide(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(100.0, 0.0),
+            PLAYER_RADIUS,
+        );
+        assert!(close(pos.x, 2.0 - PLAYER_RADIUS), "{}", pos);
+        assert!(close(pos.y, 1.0), "{}", pos);
+
+        let pos = walls.slide(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, -100.0),
+            PLAYER_RADIUS,
+        );
+        assert!(close(pos.y, PLAYER_RADIUS), "{}", pos);
+    }
+
+    #[test]
+    fn slides_along_walls() {
+        let walls = Walls::from_zones(&[ROOM]);
+
+        // Le mouvement vers le mur est annule, celui le long du mur est garde
+        let pos = walls.slide(
+            Vector2::new(1.5, 1.0),
+            Vector2::new(1.0, 0.5),
+            PLAYER_RADIUS,
+        );
+        assert!(close(pos.x, 2.0 - PLAYER_RADIUS), "{}", pos);
+        assert!(close(pos.y, 1.5), "{}", pos);
+    }
+
+    #[test]
+    fn stops_in_corners() {
+        let walls = Walls::from_zones(&[ROOM]);
+
+        let pos = walls.slide(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(5.0, 5.0),
+            PLAYER_RADIUS,
+        );
+        assert!(close(pos.x, 2.0 - PLAYER_RADIUS), "{}", pos);
+        assert!(close(pos.y, 2.0 - PLAYER_RADIUS), "{}", pos);
+    }
+
+    #[test]
+    fn passes_between_adjacent_zones() {
+        let walls = Walls::from_zones(&[ROOM, (2.0, 0.0, 4.0, 2.0)]);
+
+        let pos = walls.slide(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(2.0, 0.0),
+            PLAYER_RADIUS,
+        );
+        assert!(close(pos.x, 3.0), "{}", pos);
+        assert!(close(pos.y, 1.0), "{}", pos);
+    }
+}
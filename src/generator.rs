@@ -0,0 +1,470 @@
+use amethyst::{
+    assets::{AssetLoaderSystemData, Handle},
+    core::{math::Vector3, Parent, Transform},
+    ecs::{Entity, World},
+    prelude::{Builder, WorldExt},
+    renderer::{
+        light::{Light, PointLight},
+        loaders::load_from_srgba,
+        palette::{rgb::Rgb, Srgba},
+        rendy::mesh::{Normal, Position, Tangent, TexCoord},
+        shape::Shape,
+        Material, MaterialDefaults, Mesh, Texture,
+    },
+};
+use log::info;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    collision::Walls,
+    level::{
+        Generator, Level, COMPUTERS_PER_ROW, COMPUTER_SPACING, DESK_LEFT_OFFSET, DESK_RIGHT_OFFSET,
+    },
+    space::Zone,
+};
+
+// Memes dimensions que la salle machine modelisee
+const CORRIDOR_END: f32 = 0.65;
+const CORRIDOR_MIN_Z: f32 = -2.65;
+const CORRIDOR_MAX_Z: f32 = 0.65;
+const SEGMENT_LENGTH: f32 = 4.0;
+const DOOR_WIDTH: f32 = 0.8;
+const ENTRANCE_BACK: f32 = -7.0;
+const ROOM_BACK: f32 = -22.5;
+const FIRST_COMPUTER_Z: f32 = -7.38;
+const ROW_SPACING: f32 = 4.0;
+const MAX_ROWS: usize = 3;
+const WALL_HEIGHT: f32 = 2.6;
+// Centre des bureaux de chaque cote de l'allee, par rapport au x de la rangee
+const DESK_LEFT: f32 = -1.25;
+const DESK_RIGHT: f32 = 0.95;
+// Le joueur commence au bout du couloir, les pages sont plus loin
+const START: (f32, f32) = (CORRIDOR_END - 1.0, (CORRIDOR_MIN_Z + CORRIDOR_MAX_Z) / 2.0);
+const PAGE_START_DISTANCE: f32 = 2.5;
+
+/// Morceau de salle machine, a la fois geometrie du niveau et entites a afficher
+#[derive(Debug, Clone, PartialEq)]
+pub enum Piece {
+    /// Troncon de couloir qui s'arrete en `x`
+    CorridorSegment { x: f32 },
+    /// Porte entre le couloir et une salle, centree en `x`
+    Door { x: f32 },
+    /// Espace libre a l'entree d'une salle
+    Entrance { left: f32, right: f32 },
+    /// Allee de bureaux avec des ordinateurs des deux cotes
+    DeskRow { x: f32 },
+}
+
+impl Piece {
+    fn zone(&self) -> Zone {
+        match *self {
+            Piece::CorridorSegment { x } => (x - SEGMENT_LENGTH, CORRIDOR_MIN_Z, x, CORRIDOR_MAX_Z),
+            Piece::Door { x } => (
+                x - DOOR_WIDTH / 2.0,
+                CORRIDOR_MIN_Z - 0.7,
+                x + DOOR_WIDTH / 2.0,
+                CORRIDOR_MIN_Z,
+            ),
+            Piece::Entrance { left, right } => (left, ENTRANCE_BACK, right, CORRIDOR_MIN_Z - 0.7),
+            Piece::DeskRow { x } => (x - 0.85, ROOM_BACK, x + 0.55, ENTRANCE_BACK),
+        }
+    }
+
+    /// Sol et plafond, sous les bureaux compris
+    fn floor(&self) -> Zone {
+        match *self {
+            Piece::DeskRow { x } => (x - 2.45, ROOM_BACK, x + 1.55, ENTRANCE_BACK),
+            _ => self.zone(),
+        }
+    }
+
+    /// Paves (centre, taille, matiere) des meubles et des murs du morceau
+    fn furniture(&self) -> Vec<(Vector3<f32>, Vector3<f32>, Surface)> {
+        let mut boxes = Vec::new();
+        match *self {
+            Piece::Door { x } => {
+                let z = CORRIDOR_MIN_Z - 0.35;
+                for &side in &[-1.0, 1.0] {
+                    boxes.push((
+                        Vector3::new(x + side * (DOOR_WIDTH / 2.0 + 0.05), 1.05, z),
+                        Vector3::new(0.1, 2.1, 0.7),
+                        Surface::Frame,
+                    ));
+                }
+                boxes.push((
+                    Vector3::new(x, (2.1 + WALL_HEIGHT) / 2.0, z),
+                    Vector3::new(DOOR_WIDTH + 0.2, WALL_HEIGHT - 2.1, 0.7),
+                    Surface::Frame,
+                ));
+            }
+            // Les murs de la zone des bureaux, qui n'est pas accessible au joueur
+            Piece::Entrance { left, right } => {
+                let depth = ENTRANCE_BACK - ROOM_BACK;
+                for &side in &[left, right] {
+                    boxes.push((
+                        Vector3::new(side, WALL_HEIGHT / 2.0, ROOM_BACK + depth / 2.0),
+                        Vector3::new(0.1, WALL_HEIGHT, depth),
+                        Surface::Wall,
+                    ));
+                }
+                boxes.push((
+                    Vector3::new((left + right) / 2.0, WALL_HEIGHT / 2.0, ROOM_BACK),
+                    Vector3::new(right - left, WALL_HEIGHT, 0.1),
+                    Surface::Wall,
+                ));
+            }
+            Piece::DeskRow { x } => {
+                for k in 0..COMPUTERS_PER_ROW / 2 {
+                    let z = FIRST_COMPUTER_Z - k as f32 * COMPUTER_SPACING - 0.9;
+                    // (centre du bureau, cote de l'ecran qui donne sur l'allee)
+                    for &(desk_x, screen) in &[(x + DESK_LEFT, 0.25), (x + DESK_RIGHT, -0.25)] {
+                        boxes.push((
+                            Vector3::new(desk_x, 0.375, z),
+                            Vector3::new(0.8, 0.75, 1.6),
+                            Surface::Desk,
+                        ));
+                        boxes.push((
+                            Vector3::new(desk_x - screen, 0.95, z),
+                            Vector3::new(0.08, 0.4, 0.55),
+                            Surface::Screen,
+                        ));
+                    }
+                }
+            }
+            _ => (),
+        }
+        boxes
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Surface {
+    Floor,
+    Ceiling,
+    Wall,
+    Frame,
+    Desk,
+    Screen,
+}
+
+impl Surface {
+    const ALL: [Surface; 6] = [
+        Surface::Floor,
+        Surface::Ceiling,
+        Surface::Wall,
+        Surface::Frame,
+        Surface::Desk,
+        Surface::Screen,
+    ];
+
+    fn color(self) -> Srgba {
+        match self {
+            Surface::Floor => Srgba::new(0.3, 0.3, 0.32, 1.0),
+            Surface::Ceiling => Srgba::new(0.55, 0.55, 0.55, 1.0),
+            Surface::Wall => Srgba::new(0.7, 0.68, 0.6, 1.0),
+            Surface::Frame => Srgba::new(0.25, 0.22, 0.2, 1.0),
+            Surface::Desk => Srgba::new(0.45, 0.33, 0.2, 1.0),
+            Surface::Screen => Srgba::new(0.05, 0.05, 0.06, 1.0),
+        }
+    }
+}
+
+/// Salle machine generee a partir d'une graine
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub pieces: Vec<Piece>,
+    pub pages: Vec<(f32, f32)>,
+}
+
+pub fn generate(seed: u64, generator: &Generator, pages: usize) -> Layout {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pieces = Vec::new();
+
+    // Les salles s'alignent vers les x negatifs, le long du couloir
+    let mut right = CORRIDOR_END - 1.15;
+    for _ in 0..generator.rooms.max(1) {
+        let rows = rng.gen_range(1, MAX_ROWS + 1);
+        let first_row = right - 1.55;
+        for row in 0..rows {
+            pieces.push(Piece::DeskRow {
+                x: first_row - row as f32 * ROW_SPACING,
+            });
+        }
+
+        let left = first_row - (rows - 1) as f32 * ROW_SPACING - 2.45;
+        pieces.push(Piece::Entrance { left, right });
+
+        for _ in 0..rng.gen_range(1, 3) {
+            pieces.push(Piece::Door {
+                x: rng.gen_range(left + DOOR_WIDTH, right - DOOR_WIDTH),
+            });
+        }
+
+        // Un mur entre deux salles
+        right = left - 1.0;
+    }
+
+    let mut x = CORRIDOR_END;
+    while x > right {
+        pieces.push(Piece::CorridorSegment { x });
+        x -= SEGMENT_LENGTH;
+    }
+
+    // Les pages trainent dans le couloir et a l'entree des salles
+    let spots: Vec<Zone> = pieces
+        .iter()
+        .filter(|piece| {
+            matches!(
+                piece,
+                Piece::CorridorSegment { .. } | Piece::Entrance { .. }
+            )
+        })
+        .map(Piece::zone)
+        .collect();
+    let pages = (0..pages)
+        .map(|_| loop {
+            let zone = spots[rng.gen_range(0, spots.len())];
+            let (x, z) = (
+                rng.gen_range(zone.0 + 0.5, zone.2 - 0.5),
+                rng.gen_range(zone.1 + 0.5, zone.3 - 0.5),
+            );
+            let (dx, dz) = (x - START.0, z - START.1);
+            if dx * dx + dz * dz >= PAGE_START_DISTANCE * PAGE_START_DISTANCE {
+                break (x, z);
+            }
+        })
+        .collect();
+
+    info!("Generated machine room from seed {}", seed);
+    Layout { pieces, pages }
+}
+
+impl Layout {
+    /// Remplit la geometrie du niveau, que `space` et les systemes interrogent
+    pub fn apply(&self, level: &mut Level) {
+        level.corridors.clear();
+        level.rooms.clear();
+        level.hide_zones.clear();
+        level.computer_rows.clear();
+
+        for piece in &self.pieces {
+            match *piece {
+                Piece::CorridorSegment { .. } => level.corridors.push(piece.zone()),
+                Piece::Door { .. } | Piece::Entrance { .. } => level.rooms.push(piece.zone()),
+                Piece::DeskRow { x } => {
+                    level.hide_zones.push(piece.zone());
+                    level.computer_rows.push(x + DESK_LEFT + DESK_LEFT_OFFSET);
+                }
+            }
+        }
+
+        // Les ordinateurs se font face de chaque cote de l'allee, chacun avec sa zone
+        // au bord de l'allee et sa cachette sous le bureau dessine par `furniture`
+        level.row_offset = (DESK_LEFT + DESK_LEFT_OFFSET) - (DESK_RIGHT - DESK_RIGHT_OFFSET);
+        level.first_computer_z = FIRST_COMPUTER_Z;
+        level.start = START;
+        level.pages = self.pages.clone();
+    }
+
+    /// Cree les entites a afficher sous une racine, a supprimer avec elle
+    pub fn build(&self, world: &mut World, walls: &Walls) -> Entity {
+        let mesh = world.exec(|loader: AssetLoaderSystemData<'_, Mesh>| {
+            loader.load_from_data(
+                Shape::Cube
+                    .generate::<(Vec<Position>, Vec<Normal>, Vec<Tangent>, Vec<TexCoord>)>(Some((
+                        0.5, 0.5, 0.5,
+                    )))
+                    .into(),
+                (),
+            )
+        });
+
+        let defaults = world.read_resource::<MaterialDefaults>().0.clone();
+        let materials: Vec<Handle<Material>> = Surface::ALL
+            .iter()
+            .map(|surface| {
+                let albedo = world.exec(|loader: AssetLoaderSystemData<'_, Texture>| {
+                    loader.load_from_data(load_from_srgba(surface.color()).into(), ())
+                });
+                world.exec(|loader: AssetLoaderSystemData<'_, Material>| {
+                    loader.load_from_data(
+                        Material {
+                            albedo,
+                            ..defaults.clone()
+                        },
+                        (),
+                    )
+                })
+            })
+            .collect();
+
+        let mut boxes = Vec::new();
+        for piece in &self.pieces {
+            let (min_x, min_z, max_x, max_z) = piece.floor();
+            let center = ((min_x + max_x) / 2.0, (min_z + max_z) / 2.0);
+            let size = (max_x - min_x, max_z - min_z);
+            boxes.push((
+                Vector3::new(center.0, -0.05, center.1),
+                Vector3::new(size.0, 0.1, size.1),
+                Surface::Floor,
+            ));
+            boxes.push((
+                Vector3::new(center.0, WALL_HEIGHT + 0.05, center.1),
+                Vector3::new(size.0, 0.1, size.1),
+                Surface::Ceiling,
+            ));
+            boxes.extend(piece.furniture());
+        }
+
+        for segment in &walls.segments {
+            let center = (segment.a + segment.b) / 2.0;
+            // Les bords des allees donnent sur les bureaux
+            if center.y <= ENTRANCE_BACK + 1.0e-3 {
+                continue;
+            }
+
+            let edge = segment.b - segment.a;
+            boxes.push((
+                Vector3::new(center.x, WALL_HEIGHT / 2.0, center.y),
+                Vector3::new(edge.x.abs().max(0.1), WALL_HEIGHT, edge.y.abs().max(0.1)),
+                Surface::Wall,
+            ));
+        }
+
+        let root = world.create_entity().with(Transform::default()).build();
+
+        for (center, size, surface) in boxes {
+            let mut transform = Transform::default();
+            transform.set_translation(center);
+            transform.set_scale(size);
+
+            world
+                .create_entity()
+                .with(mesh.clone())
+                .with(materials[surface as usize].clone())
+                .with(transform)
+                .with(Parent::new(root))
+                .build();
+        }
+
+        // Une veilleuse par troncon de couloir et par salle
+        for piece in &self.pieces {
+            if let Piece::CorridorSegment { .. } | Piece::Entrance { .. } = piece {
+                let (min_x, min_z, max_x, max_z) = piece.zone();
+                let mut transform = Transform::default();
+                transform.set_translation_xyz(
+                    (min_x + max_x) / 2.0,
+                    WALL_HEIGHT - 0.2,
+                    (min_z + max_z) / 2.0,
+                );
+
+                let light: Light = PointLight {
+                    color: Rgb::new(0.8, 0.85, 1.0),
+                    intensity: 1.5,
+                    radius: 6.0,
+                    smoothness: 1.0,
+                }
+                .into();
+
+                world
+                    .create_entity()
+                    .with(light)
+                    .with(transform)
+                    .with(Parent::new(root))
+                    .build();
+            }
+        }
+
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::is_in_zone;
+
+    fn generated_level(seed: u64) -> (Layout, Level) {
+        let layout = generate(seed, &Generator::default(), 3);
+        let mut level = Level::default();
+        layout.apply(&mut level);
+        (layout, level)
+    }
+
+    #[test]
+    fn same_seed_same_layout() {
+        for seed in 0..20 {
+            let generator = Generator::default();
+            assert_eq!(generate(seed, &generator, 3), generate(seed, &generator, 3));
+        }
+    }
+
+    #[test]
+    fn pages_and_computers_are_reachable() {
+        for seed in 0..50 {
+            let (layout, level) = generated_level(seed);
+            let zones = level.walkable_zones();
+            let walkable = |x, z| zones.iter().any(|zone| is_in_zone(zone, x, z));
+
+            let (start_x, start_z) = level.start;
+            assert!(walkable(start_x, start_z), "seed {}", seed);
+            for &(x, z) in &level.pages {
+                assert!(walkable(x, z), "seed {}: page ({}, {})", seed, x, z);
+                assert!(
+                    (x - start_x).hypot(z - start_z) >= PAGE_START_DISTANCE,
+                    "seed {}: page ({}, {}) at the start",
+                    seed,
+                    x,
+                    z
+                );
+            }
+
+            let desks: Vec<(Vector3<f32>, Vector3<f32>)> = layout
+                .pieces
+                .iter()
+                .flat_map(Piece::furniture)
+                .filter(|(_, _, surface)| matches!(surface, Surface::Desk))
+                .map(|(center, size, _)| (center, size))
+                .collect();
+
+            let mut triggers = Vec::new();
+            for computer_id in 0..level.computer_number() {
+                // Toute la zone d'utilisation est dans l'allee
+                let (x, z) = level.computer_position(computer_id);
+                for &(dx, dz) in &[
+                    (-0.34, -0.01),
+                    (-0.01, -0.01),
+                    (-0.34, -1.79),
+                    (-0.01, -1.79),
+                ] {
+                    assert!(walkable(x + dx, z + dz), "seed {}: {}", seed, computer_id);
+                }
+                assert!(
+                    !triggers.contains(&(x, z)),
+                    "seed {}: {}",
+                    seed,
+                    computer_id
+                );
+                triggers.push((x, z));
+
+                // La cachette est sous un bureau dessine, au bord de l'allee
+                let (desk_x, desk_z, _) = level.desk_position(computer_id);
+                assert!(
+                    desks.iter().any(|(center, size)| {
+                        (desk_x - center.x).abs() < size.x / 2.0
+                            && (desk_z - center.z).abs() < size.z / 2.0
+                    }),
+                    "seed {}: desk of {}",
+                    seed,
+                    computer_id
+                );
+                assert!(
+                    walkable(x - 0.175, desk_z),
+                    "seed {}: {}",
+                    seed,
+                    computer_id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use amethyst::{config::Config, core::Transform};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    save::data_path,
+    space::{is_in_zone, Zone},
+};
+
+// Ordinateurs par rangee, la moitie de chaque cote de l'allee
+pub const COMPUTERS_PER_ROW: usize = 8;
+pub const COMPUTER_SPACING: f32 = 4.1;
+// Distance en x entre le coin de la zone de l'ordinateur et la cachette sous son bureau
+pub const DESK_LEFT_OFFSET: f32 = 0.75;
+pub const DESK_RIGHT_OFFSET: f32 = 0.4;
+
+/// Reglages de difficulte propres a un niveau
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Difficulty {
+    /// Delai minimum (en secondes) avant la premiere venue de Bashar
+    pub first_screamer: f64,
+    /// Multiplie le delai entre deux venues de Bashar
+    pub screamer_delay: f64,
+    /// Delai minimum (en secondes) entre deux ordinateurs deverrouilles, jusqu'au double
+    pub unlock_delay: u64,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty {
+            first_screamer: 15.0,
+            screamer_delay: 1.0,
+            unlock_delay: 6,
+        }
+    }
+}
+
+/// Ce qu'il faut recuperer pour finir le niveau
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Objective {
+    pub codes: u8,
+    pub pages: usize,
+}
+
+impl Default for Objective {
+    fn default() -> Self {
+        Objective {
+            codes: 10,
+            pages: 0,
+        }
+    }
+}
+
+/// Parametres de la salle generee par `generator::generate`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Generator {
+    /// Nouvelle graine a chaque partie si absente
+    pub seed: Option<u64>,
+    pub rooms: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator {
+            seed: None,
+            rooms: 2,
+        }
+    }
+}
+
+fn default_row_offset() -> f32 {
+    2.5
+}
+
+/// Une scene gltf et la geometrie qui va avec, voir `assets/levels.ron`
+///
+/// Avec un `generator`, la scene et la geometrie sont generees au debut de la partie.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Level {
+    /// Position dans `Levels`, remplie au chargement
+    #[serde(skip)]
+    pub index: usize,
+    pub name: String,
+    /// Nom de la scene dans le manifeste
+    #[serde(default)]
+    pub scene: String,
+    #[serde(default)]
+    pub generator: Option<Generator>,
+    /// Position (x, z) du joueur au debut du niveau
+    #[serde(default)]
+    pub start: (f32, f32),
+    /// Zones ou les pas resonnent
+    #[serde(default)]
+    pub corridors: Vec<Zone>,
+    #[serde(default)]
+    pub rooms: Vec<Zone>,
+    /// Allees entre les bureaux, depuis lesquelles on peut se cacher
+    #[serde(default)]
+    pub hide_zones: Vec<Zone>,
+    /// x de chaque rangee d'ordinateurs
+    #[serde(default)]
+    pub computer_rows: Vec<f32>,
+    /// Decalage en x de la seconde moitie de chaque rangee
+    #[serde(default = "default_row_offset")]
+    pub row_offset: f32,
+    /// z du premier ordinateur de chaque rangee
+    #[serde(default)]
+    pub first_computer_z: f32,
+    /// Pages d'AFIT (x, z) a ramasser
+    #[serde(default)]
+    pub pages: Vec<(f32, f32)>,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub objective: Objective,
+}
+
+impl Level {
+    /// Toutes les zones dans lesquelles le joueur peut marcher
+    pub fn walkable_zones(&self) -> Vec<Zone> {
+        self.corridors
+            .iter()
+            .chain(self.rooms.iter())
+            .chain(self.hide_zones.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Rectangle englobant tout le niveau
+    pub fn bounds(&self) -> Zone {
+        self.walkable_zones().iter().fold(
+            (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+            |(min_x, min_z, max_x, max_z), zone| {
+                (
+                    min_x.min(zone.0),
+                    min_z.min(zone.1),
+                    max_x.max(zone.2),
+                    max_z.max(zone.3),
+                )
+            },
+        )
+    }
+
+    /// Partie du niveau ou se trouve `x`, pour le journal
+    pub fn side(&self, x: f32) -> &'static str {
+        let (min_x, _, max_x, _) = self.bounds();
+        if x < (min_x + max_x) / 2.0 {
+            "a gauche"
+        } else {
+            "a droite"
+        }
+    }
+
+    pub fn is_in_corridor(&self, x: f32, z: f32) -> bool {
+        self.corridors.iter().any(|zone| is_in_zone(zone, x, z))
+    }
+
+    pub fn is_close_from_computer(&self, x: f32, z: f32) -> bool {
+        self.hide_zones.iter().any(|zone| is_in_zone(zone, x, z))
+    }
+
+    pub fn computer_number(&self) -> usize {
+        self.computer_rows.len() * COMPUTERS_PER_ROW
+    }
+
+    /// Coin (x, z) de la zone depuis laquelle on peut utiliser l'ordinateur
+    pub fn computer_position(&self, computer_id: usize) -> (f32, f32) {
+        let trigger_x = {
+            let mut row_x = self.computer_rows[computer_id / COMPUTERS_PER_ROW];
+            if computer_id % COMPUTERS_PER_ROW >= COMPUTERS_PER_ROW / 2 {
+                row_x -= self.row_offset;
+            }
+            row_x
+        };
+        let trigger_z = self.first_computer_z
+            - (computer_id % (COMPUTERS_PER_ROW / 2)) as f32 * COMPUTER_SPACING;
+
+        (trigger_x, trigger_z)
+    }
+
+    pub fn is_able_to_use_computer(
+        &self,
+        player_transform: &Transform,
+        computer_id: usize,
+    ) -> bool {
+        let (trigger_x, trigger_z) = self.computer_position(computer_id);
+
+        let pos = player_transform.translation();
+        pos.x >= trigger_x - 0.35
+            && pos.z >= trigger_z - 1.8
+            && pos.x <= trigger_x
+            && pos.z <= trigger_z
+    }
+
+    /// Position (x, z) sous le bureau de l'ordinateur et direction du regard vers l'allee
+    pub fn desk_position(&self, computer_id: usize) -> (f32, f32, f32) {
+        let (trigger_x, trigger_z) = self.computer_position(computer_id);
+        let z = trigger_z - 0.9;
+
+        // Les premiers ordinateurs d'une rangee ont leur bureau a gauche de l'allee
+        if computer_id % COMPUTERS_PER_ROW < COMPUTERS_PER_ROW / 2 {
+            (trigger_x - DESK_LEFT_OFFSET, z, -FRAC_PI_2)
+        } else {
+            (trigger_x + DESK_RIGHT_OFFSET, z, FRAC_PI_2)
+        }
+    }
+
+    pub fn nearest_desk(&self, x: f32, z: f32) -> Option<(f32, f32, f32)> {
+        (0..self.computer_number())
+            .map(|computer_id| self.desk_position(computer_id))
+            .min_by(|a, b| {
+                let da = (a.0 - x).powi(2) + (a.1 - z).powi(2);
+                let db = (b.0 - x).powi(2) + (b.1 - z).powi(2);
+                da.partial_cmp(&db).unwrap()
+            })
+    }
+}
+
+/// Tous les niveaux, dans l'ordre ou ils se debloquent
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Levels(pub Vec<Level>);
+
+pub fn load_levels() -> Result<Levels, String> {
+    let path = data_path("assets/levels.ron").ok_or("Dossier du jeu introuvable")?;
+    let mut levels = Levels::load(&path).map_err(|e| format!("{} : {}", path.display(), e))?;
+
+    if levels.0.is_empty() {
+        return Err(format!("{} : aucun niveau", path.display()));
+    }
+    for (index, level) in levels.0.iter_mut().enumerate() {
+        level.index = index;
+    }
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate;
+
+    #[test]
+    fn levels_file_loads() {
+        let levels = load_levels().unwrap();
+
+        for (index, level) in levels.0.iter().enumerate() {
+            assert_eq!(level.index, index);
+            assert!(level.generator.is_some() || !level.scene.is_empty());
+
+            // Assez d'ordinateurs pour l'objectif, sauf si la graine change a chaque partie
+            let mut level = level.clone();
+            if let Some(generator) = &level.generator {
+                match generator.seed {
+                    Some(seed) => generate(seed, generator, 0).apply(&mut level),
+                    None => continue,
+                }
+            }
+            assert!(
+                level.objective.codes as usize <= level.computer_number(),
+                "{}",
+                level.name
+            );
+        }
+        assert!(levels.0.iter().any(|level| level.generator.is_some()));
+    }
+}
@@ -1,31 +1,62 @@
 use amethyst::{
     animation::VertexSkinningBundle,
-    assets::{AssetStorage, ProgressCounter},
+    assets::{AssetStorage, HotReloadBundle, HotReloadStrategy},
     audio::{output::Output, AudioBundle, Source, SourceHandle},
-    controls::ArcBallControlBundle,
-    core::TransformBundle,
+    controls::{CursorHideSystemDesc, MouseFocusUpdateSystemDesc},
+    core::{HideHierarchySystemDesc, TransformBundle},
     ecs::Read,
     input::{InputBundle, StringBindings},
     prelude::*,
     renderer::{
-        plugins::{RenderShaded3D, RenderSkybox, RenderToWindow},
+        plugins::{RenderFlat2D, RenderShaded3D, RenderSkybox, RenderToWindow},
         types::DefaultBackend,
         RenderingBundle,
     },
     ui::{RenderUi, UiBundle},
     utils::{application_root_dir, auto_fov::AutoFovSystem},
+    window::DisplayConfig,
 };
 use amethyst_gltf::GltfSceneLoaderSystemDesc;
 
+mod assets;
+mod collision;
+mod generator;
+mod level;
+mod save;
+mod settings;
 mod space;
 mod states;
+mod stats;
 mod systems;
 mod ui;
 
+use settings::load_settings;
 use states::loading::LoadingState;
 use systems::{
-    computer::ComputerSystem, hide::HidingSystem, movement::RuptureMovementSystem,
-    screamer::ScreamerSystem, use_system::UseSystem,
+    audio::AudioCueSystemDesc,
+    computer::ComputerSystem,
+    effects::CameraEffectsSystemDesc,
+    event_log::EventLogSystemDesc,
+    flashlight::{BatterySystem, FlashlightSystem},
+    graphics::GraphicsSystem,
+    graphics_menu::GraphicsMenuSystem,
+    hide::HidingSystem,
+    hud::HudScaleSystem,
+    look::MouseLookSystemDesc,
+    minimap::MinimapSystem,
+    movement::RuptureMovementSystem,
+    objective::{EscapeSystem, ObjectiveSystem},
+    pages::PageSystem,
+    panic::PanicSystem,
+    phase::{InPhases, InPhasesDesc},
+    posture::PostureSystem,
+    reload::AssetReloadSystem,
+    save::SaveSystem,
+    screamer::ScreamerSystemDesc,
+    stamina::StaminaSystem,
+    stats::StatsSystemDesc,
+    subtitles::SubtitleSystemDesc,
+    use_system::UseSystem,
 };
 use ui::TextSystem;
 
@@ -39,7 +70,15 @@ fn main() -> amethyst::Result<()> {
     let display_config_path = config_dir.join("display.ron");
     let key_bindings_path = config_dir.join("input.ron");
 
-    let game_data = GameDataBuilder::default()
+    // La taille de la fenetre vient des reglages du joueur
+    let settings = load_settings();
+    let mut display_config = DisplayConfig::load(&display_config_path)?;
+    display_config.dimensions = Some(settings.resolution);
+
+    // Rechargement a chaud des assets pendant le developpement
+    let dev = std::env::args().any(|arg| arg == "--dev");
+
+    let mut game_data = GameDataBuilder::default()
         .with(AutoFovSystem::default(), "auto_fov", &[])
         .with_system_desc(
             GltfSceneLoaderSystemDesc::default(),
@@ -47,21 +86,82 @@ fn main() -> amethyst::Result<()> {
             &[], // This is important so that entity instantiation is performed in a single frame.
         )
         .with(
-            RuptureMovementSystem::new(
+            InPhases(RuptureMovementSystem::new(
                 2.5,
                 Some(String::from("move_x")),
                 Some(String::from("move_z")),
-            ),
+            )),
             "rupture_movement",
             &[],
         )
-        .with(ComputerSystem, "computer", &[])
-        .with(ScreamerSystem, "screamer", &[])
-        .with(HidingSystem, "hiding", &[])
-        .with(TextSystem, "text", &[])
-        .with(UseSystem, "use", &[])
-        .with_bundle(ArcBallControlBundle::<StringBindings>::new().with_sensitivity(0.1, 0.1))?
-        .with_bundle(TransformBundle::new().with_dep(&["arc_ball_rotation"]))?
+        .with(InPhases(PostureSystem), "posture", &["rupture_movement"])
+        .with(InPhases(StaminaSystem), "stamina", &["rupture_movement"])
+        .with(InPhases(ComputerSystem), "computer", &[])
+        .with_system_desc(
+            InPhasesDesc(ScreamerSystemDesc),
+            "screamer",
+            &["rupture_movement"],
+        )
+        .with(InPhases(TextSystem), "text", &[])
+        .with(
+            InPhases(GraphicsMenuSystem::default()),
+            "graphics_menu",
+            &["text"],
+        )
+        .with(GraphicsSystem::default(), "graphics", &["graphics_menu"])
+        .with(InPhases(UseSystem), "use", &[])
+        .with(
+            InPhases(PageSystem::default()),
+            "pages",
+            &["rupture_movement", "use"],
+        )
+        .with(MinimapSystem, "minimap", &[])
+        .with(InPhases(SaveSystem::default()), "save", &["use", "pages"])
+        .with(ObjectiveSystem, "objective", &["use", "pages"])
+        .with(InPhases(EscapeSystem), "escape", &["use", "pages"])
+        .with_system_desc(
+            EventLogSystemDesc,
+            "event_log",
+            &["computer", "use", "pages", "screamer"],
+        )
+        .with_system_desc(AudioCueSystemDesc, "audio_cues", &["computer", "screamer"])
+        .with_system_desc(
+            SubtitleSystemDesc,
+            "subtitles",
+            &["computer", "screamer", "rupture_movement"],
+        )
+        .with(InPhases(HidingSystem), "hiding", &["posture"])
+        .with_system_desc(
+            StatsSystemDesc,
+            "stats",
+            &["use", "pages", "hiding", "screamer"],
+        )
+        .with(
+            HudScaleSystem::default(),
+            "hud_scale",
+            &["text", "minimap", "stamina"],
+        )
+        .with_system_desc(MouseLookSystemDesc, "mouse_look", &["hiding"])
+        .with_system_desc(MouseFocusUpdateSystemDesc, "mouse_focus", &["mouse_look"])
+        .with_system_desc(CursorHideSystemDesc, "cursor_hide", &["mouse_focus"])
+        .with(PanicSystem, "panic", &["hiding"])
+        .with_system_desc(
+            CameraEffectsSystemDesc,
+            "camera_effects",
+            &["mouse_look", "screamer"],
+        )
+        .with(InPhases(BatterySystem), "battery", &[])
+        .with(
+            FlashlightSystem,
+            "flashlight",
+            &["camera_effects", "battery"],
+        )
+        .with_bundle(TransformBundle::new().with_dep(&["camera_effects"]))?
+        .with_system_desc(
+            HideHierarchySystemDesc,
+            "hide_hierarchy",
+            &["parent_hierarchy_system"],
+        )
         .with_bundle(
             InputBundle::<StringBindings>::new().with_bindings_from_file(&key_bindings_path)?,
         )?
@@ -69,35 +169,35 @@ fn main() -> amethyst::Result<()> {
         .with_bundle(
             RenderingBundle::<DefaultBackend>::new()
                 .with_plugin(
-                    RenderToWindow::from_config_path(display_config_path)?
-                        .with_clear([0.34, 0.36, 0.52, 1.0]),
+                    RenderToWindow::from_config(display_config).with_clear([0.34, 0.36, 0.52, 1.0]),
                 )
                 .with_plugin(RenderShaded3D::default())
+                .with_plugin(RenderFlat2D::default())
                 .with_plugin(RenderUi::default())
                 .with_plugin(RenderSkybox::default()),
         )?
         .with_bundle(AudioBundle::default())?
         .with_bundle(UiBundle::<StringBindings>::new())?;
 
-    let mut game = Application::new(
-        assets_dir,
-        LoadingState {
-            progress_counter: ProgressCounter::default(),
-            scene: None,
-            screamer: None,
-            coming: None,
-            boot: None,
-            font: None,
-            afit: None,
-            bashar: None,
-        },
-        game_data,
-    )?;
+    if dev {
+        game_data = game_data
+            .with_bundle(HotReloadBundle::new(HotReloadStrategy::every(2)))?
+            .with(AssetReloadSystem::default(), "asset_reload", &[]);
+    }
+
+    let mut game = Application::build(assets_dir, LoadingState::default())?
+        .with_resource(settings)
+        .with_resource(DevMode(dev))
+        .build(game_data)?;
     game.run();
 
     Ok(())
 }
 
+/// Lance avec `--dev`, les assets modifies sont recharges sans redemarrer
+#[derive(Default)]
+pub struct DevMode(pub bool);
+
 fn play<'s>(
     storage: &Read<'s, AssetStorage<Source>>,
     handle: &Option<SourceHandle>,
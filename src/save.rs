@@ -0,0 +1,129 @@
+use amethyst::{
+    config::Config,
+    core::Transform,
+    ecs::{Join, ReadStorage, WriteStorage},
+    utils::application_root_dir,
+};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::{
+    level::Level,
+    states::game::{Afit, Player, PlayerHidden, TimeToScreamer, UnlockedComputers},
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    /// Indice du niveau dans `Levels`
+    #[serde(default)]
+    pub level: usize,
+    /// Graine de la salle, si elle est generee
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub afit: Afit,
+    pub unlocked_computers: UnlockedComputers,
+    pub time_to_screamer: TimeToScreamer,
+    pub player_hidden: PlayerHidden,
+    pub player_position: [f32; 3],
+    /// Temps absolu (en secondes) au moment de la sauvegarde
+    pub saved_at: f64,
+}
+
+impl SaveGame {
+    pub fn new(
+        level: &Level,
+        afit: &Afit,
+        uc: &UnlockedComputers,
+        since: &TimeToScreamer,
+        hidden: &PlayerHidden,
+        player: &Transform,
+        now: f64,
+    ) -> Self {
+        let pos = player.translation();
+
+        SaveGame {
+            level: level.index,
+            seed: level
+                .generator
+                .as_ref()
+                .and_then(|generator| generator.seed),
+            afit: afit.clone(),
+            unlocked_computers: uc.clone(),
+            time_to_screamer: since.clone(),
+            player_hidden: hidden.clone(),
+            player_position: [pos.x, pos.y, pos.z],
+            saved_at: now,
+        }
+    }
+
+    /// Decale tous les temps absolus de la sauvegarde pour qu'ils partent de `now`
+    pub fn rebase(&mut self, now: f64) {
+        let shift = now - self.saved_at;
+        self.time_to_screamer.shift(shift);
+        self.unlocked_computers.shift(shift);
+        self.saved_at = now;
+    }
+
+    /// Replace le joueur a la position sauvegardee
+    pub fn place_player(
+        &self,
+        transforms: &mut WriteStorage<'_, Transform>,
+        players: &ReadStorage<'_, Player>,
+    ) {
+        let [x, _, z] = self.player_position;
+        for (transform, _) in (transforms, players).join() {
+            transform.set_translation_x(x);
+            transform.set_translation_z(z);
+        }
+    }
+}
+
+/// Chemin d'un fichier de donnees a cote de l'executable
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    application_root_dir().ok().map(|root| root.join(file))
+}
+
+pub fn load_game() -> Option<SaveGame> {
+    let path = data_path("save.ron")?;
+    if !path.exists() {
+        return None;
+    }
+
+    match SaveGame::load(&path) {
+        Ok(save) => Some(save),
+        Err(e) => {
+            warn!("Failed to load save {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Efface la sauvegarde du niveau `level`, terminee elle ne servirait plus
+pub fn delete_game(level: usize) {
+    let path = match data_path("save.ron") {
+        Some(path) if path.exists() => path,
+        _ => return,
+    };
+
+    // La sauvegarde d'un autre niveau est gardee
+    if let Some(save) = load_game() {
+        if save.level != level {
+            return;
+        }
+    }
+
+    match std::fs::remove_file(&path) {
+        Ok(()) => info!("Save {} deleted", path.display()),
+        Err(e) => warn!("Failed to delete save {}: {}", path.display(), e),
+    }
+}
+
+pub fn write_game(save: &SaveGame) {
+    if let Some(path) = data_path("save.ron") {
+        match save.write(&path) {
+            Ok(()) => info!("Game saved to {}", path.display()),
+            Err(e) => warn!("Failed to write save {}: {}", path.display(), e),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use amethyst::config::Config;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::save::data_path;
+
+/// Force des mouvements de camera, `Off` pour les joueurs sensibles au mal des transports
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EffectIntensity {
+    Off,
+    Reduced,
+    Full,
+}
+
+impl EffectIntensity {
+    pub fn factor(self) -> f32 {
+        match self {
+            EffectIntensity::Off => 0.0,
+            EffectIntensity::Reduced => 0.4,
+            EffectIntensity::Full => 1.0,
+        }
+    }
+}
+
+/// Resolutions proposees dans le menu des graphismes
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (1024, 768),
+    (1280, 720),
+    (1280, 1024),
+    (1600, 900),
+    (1920, 1080),
+];
+
+pub const MIN_FOV: f32 = 50.;
+pub const MAX_FOV: f32 = 100.;
+pub const MIN_BRIGHTNESS: f32 = 0.5;
+pub const MAX_BRIGHTNESS: f32 = 2.;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Degres de rotation par pixel de deplacement de la souris
+    pub mouse_sensitivity: f32,
+    pub invert_y: bool,
+    pub camera_effects: EffectIntensity,
+    pub fullscreen: bool,
+    /// Taille de la fenetre hors plein ecran
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    /// Champ de vision vertical (en degres)
+    pub fov: f32,
+    pub brightness: f32,
+    /// Sous-titres des sons et flash a l'ecran quand un ordinateur demarre
+    pub subtitles: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            mouse_sensitivity: 0.1,
+            invert_y: false,
+            camera_effects: EffectIntensity::Full,
+            fullscreen: false,
+            resolution: (1024, 768),
+            vsync: true,
+            fov: 60.,
+            brightness: 1.,
+            subtitles: true,
+        }
+    }
+}
+
+// Reglages du joueur, a cote de la sauvegarde, et reglages livres avec le jeu
+const PLAYER_SETTINGS: &str = "settings.ron";
+const DEFAULT_SETTINGS: &str = "config/settings.ron";
+
+/// Les reglages du joueur, sinon ceux livres avec le jeu
+pub fn load_settings() -> Settings {
+    let path = match [PLAYER_SETTINGS, DEFAULT_SETTINGS]
+        .iter()
+        .filter_map(|file| data_path(file))
+        .find(|path| path.exists())
+    {
+        Some(path) => path,
+        None => return Settings::default(),
+    };
+
+    Settings::load(&path).unwrap_or_else(|e| {
+        warn!("Failed to load settings {}: {}", path.display(), e);
+        Settings::default()
+    })
+}
+
+/// Le fichier livre avec le jeu n'est jamais reecrit
+pub fn write_settings(settings: &Settings) {
+    if let Some(path) = data_path(PLAYER_SETTINGS) {
+        if let Err(e) = settings.write(&path) {
+            warn!("Failed to write settings {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use amethyst::{
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+};
+
+use crate::ui::create_error_text;
+
+/// Remplace le jeu quand des assets n'ont pas pu etre charges
+pub struct ErrorState {
+    /// (chemin, erreur) de chaque asset en echec
+    pub errors: Vec<(String, String)>,
+}
+
+impl SimpleState for ErrorState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let mut message = "Oups, impossible de charger le jeu :\n\n".to_string();
+        for (path, error) in &self.errors {
+            message += &format!("{} : {}\n", path, error);
+        }
+        message += "\nVerifiez le dossier 'assets' puis relancez le jeu.\n\
+            Appuyez sur 'Echap' pour quitter";
+
+        create_error_text(data.world, message);
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) || is_key_down(event, VirtualKeyCode::Escape) {
+                return Trans::Quit;
+            }
+        }
+        Trans::None
+    }
+}
@@ -1,125 +1,437 @@
 use amethyst::{
-    assets::Handle,
-    audio::SourceHandle,
     controls::{FlyControlTag, HideCursor},
-    core::{math::Vector3, Transform},
-    ecs::{Entity, World},
+    core::{math::Vector3, HiddenPropagate, Parent, Time, Transform},
+    ecs::{Component, DenseVecStorage, Entity, NullStorage, World},
     input::{is_key_down, is_mouse_button_down, VirtualKeyCode},
     prelude::*,
     renderer::{
-        light::{Light, PointLight},
+        light::{Light, SpotLight},
         palette::rgb::Rgb,
         Camera, SpriteRender,
     },
-    ui::{Anchor, FontHandle, UiImage, UiTransform},
+    shrev::{EventChannel, ReaderId},
+    ui::{Anchor, Stretch, UiImage, UiText, UiTransform},
+    window::ScreenDimensions,
     winit::MouseButton,
 };
-use amethyst_gltf::GltfSceneAsset;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::ui::{self, *};
+use super::level_select::LevelSelectState;
+use crate::{
+    assets::GameAssets,
+    collision::Walls,
+    generator::generate,
+    level::{Levels, Objective},
+    save::{delete_game, SaveGame},
+    stats::{load_stats, write_stats, CurrentRun, Stats},
+    ui::{self, *},
+};
+
+pub const CAMERA_HEIGHT: f32 = 1.2;
+// Pages posees dans une salle generee, au moins autant que l'objectif
+const GENERATED_PAGES: usize = 3;
 
-pub const MAX_CODE: u8 = 10;
+// Secondes de screamer avant de quitter le jeu
+const CAUGHT_DELAY: f64 = 3.5;
 
 pub struct GameState {
-    pub scene: Handle<GltfSceneAsset>,
-    pub screamer: SourceHandle,
-    pub coming: SourceHandle,
-    pub boot: SourceHandle,
-    pub font: FontHandle,
-    pub afit: SpriteRender,
-    pub bashar: UiImage,
+    /// Indice du niveau dans `Levels`
+    pub level: usize,
+    pub save: Option<SaveGame>,
+    events: Option<ReaderId<GameEvent>>,
+    /// Moment ou Bashar a attrape le joueur
+    caught_at: Option<f64>,
+    /// Debut de la pause en cours
+    paused_at: Option<f64>,
 }
 
-#[derive(Default)]
+impl GameState {
+    pub fn new(level: usize, save: Option<SaveGame>) -> Self {
+        GameState {
+            level,
+            save,
+            events: None,
+            caught_at: None,
+            paused_at: None,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Afit {
     pub code_found: u8,
+    /// Pages d'AFIT deja ramassees (indices dans `Level::pages`)
+    #[serde(default)]
+    pub pages: Vec<usize>,
 }
 
-#[derive(Default)]
+impl Afit {
+    pub fn is_complete(&self, objective: &Objective) -> bool {
+        self.code_found >= objective.codes && self.pages.len() >= objective.pages
+    }
+}
+
+/// Page d'AFIT a ramasser, toujours tournee vers le joueur
+pub struct AfitPage {
+    pub id: usize,
+}
+
+impl Component for AfitPage {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct UnlockedComputers {
     pub unlocked_computers: Vec<usize>,
     pub last_unlock_time: Duration,
 }
 
+impl UnlockedComputers {
+    /// Decale le prochain deverrouillage de `shift` secondes
+    pub fn shift(&mut self, shift: f64) {
+        self.last_unlock_time = if shift >= 0.0 {
+            self.last_unlock_time + Duration::from_secs_f64(shift)
+        } else {
+            self.last_unlock_time
+                .checked_sub(Duration::from_secs_f64(-shift))
+                .unwrap_or_default()
+        };
+    }
+}
+
+/// Entite racine de la scene gltf, recreee quand le fichier est recharge
+#[derive(Default)]
+pub struct SceneRoot(pub Option<Entity>);
+
 #[derive(Default)]
 pub struct Screamer {
     pub bashar: Option<Entity>,
 }
 
-#[derive(Default)]
+/// Les anciennes sauvegardes ont aussi un `last_displayed`, ignore au chargement
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct TimeToScreamer {
     pub at: f64,
     pub played: bool,
-    pub last_displayed: f64,
-    pub display: bool,
+}
+
+impl TimeToScreamer {
+    /// Decale la venue de Bashar de `shift` secondes
+    pub fn shift(&mut self, shift: f64) {
+        if self.at != 0.0 {
+            self.at += shift;
+        }
+    }
+}
+
+/// Ou en est la partie, chaque systeme de jeu declare les phases ou il tourne
+/// (voir `systems::phase`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum GamePhase {
+    /// Texte d'introduction, statistiques et reglages graphiques
+    #[default]
+    Intro,
+    Playing,
+    /// Echap, la souris est liberee et le temps de jeu s'arrete
+    Paused,
+    /// Bashar a attrape le joueur
+    Caught,
+    /// Sorti par le couloir une fois l'objectif rempli
+    Won,
+}
+
+/// Entite du joueur, parente de la camera (elle meme parente de la lampe)
+#[derive(Default)]
+pub struct Player;
+
+impl Component for Player {
+    type Storage = NullStorage<Self>;
 }
 
 #[derive(Default)]
 pub struct PlayerLight(pub Option<Entity>);
 
+/// Orientation du regard (en radians), appliquee par le MouseLookSystem
 #[derive(Default)]
-pub struct Sounds {
-    pub screamer: Option<SourceHandle>,
-    pub coming: Option<SourceHandle>,
-    pub boot: Option<SourceHandle>,
+pub struct Look {
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+pub struct Flashlight {
+    pub on: bool,
+    /// Entre 0 (vide) et 1 (pleine)
+    pub battery: f32,
+    pub pressed: bool,
+}
+
+impl Default for Flashlight {
+    fn default() -> Self {
+        Flashlight {
+            on: false,
+            battery: 1.0,
+            pressed: false,
+        }
+    }
+}
+
+// Au dela, Bashar entend le joueur respirer
+pub const PANIC_BREATHING: f32 = 0.75;
+
+#[derive(Default)]
+pub struct Panic {
+    /// Entre 0 (calme) et 1 (le joueur craque et sort de sa cachette)
+    pub value: f32,
+    pub last_heartbeat: f64,
 }
 
 #[derive(Default)]
+pub struct Posture {
+    /// Entre 0 (debout) et 1 (accroupi)
+    pub crouch: f32,
+    /// Entre -1 (penche a gauche) et 1 (penche a droite)
+    pub lean: f32,
+    pub crouching: bool,
+}
+
+pub struct Stamina {
+    /// Entre 0 (epuise) et 1 (repose)
+    pub value: f32,
+    pub sprinting: bool,
+    /// A bout de souffle, plus de sprint avant d'avoir un peu recupere
+    pub exhausted: bool,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Stamina {
+            value: 1.0,
+            sprinting: false,
+            exhausted: false,
+        }
+    }
+}
+
+/// Ce qui arrive pendant la partie, publie par les systemes qui le constatent
+///
+/// Le son, le HUD, les statistiques et le `GameState` s'y abonnent plutot que de
+/// surveiller les ressources des autres systemes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    ComputerUnlocked(usize),
+    CodeCollected,
+    PageCollected,
+    PlayerHid,
+    BasharComing,
+    BasharLeft,
+    PlayerCaught,
+    /// Sorti par le couloir une fois l'objectif rempli
+    GameWon,
+}
+
+/// Bruit fait par le joueur, que Bashar peut entendre
+#[derive(Debug, Clone)]
+pub struct Noise {
+    /// Entre 0 (silence) et 1 (sprint)
+    pub loudness: f32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct PlayerHidden {
     pub hidden: bool,
     pub can_hide: bool,
+    #[serde(skip)]
     pub pressed: bool,
+    /// Entre 0 (debout) et 1 (sous le bureau), le joueur est vulnerable entre les deux
+    #[serde(skip)]
+    pub progress: f32,
+    /// Position (x, y, z) de la camera avant de se cacher, y etant relatif au joueur
+    #[serde(skip)]
+    pub from: (f32, f32, f32),
+    /// Cachette (x, z, direction du regard)
+    #[serde(skip)]
+    pub spot: (f32, f32, f32),
+}
+
+impl PlayerHidden {
+    pub fn is_safe(&self) -> bool {
+        self.hidden && self.progress >= 1.0
+    }
+
+    /// Caché ou en train de se cacher / de sortir
+    pub fn is_busy(&self) -> bool {
+        self.hidden || self.progress > 0.0
+    }
+}
+
+pub fn scene_transform() -> Transform {
+    let mut transform = Transform::default();
+    transform.set_scale(Vector3::new(2.0, 2.0, 2.0));
+    transform
 }
 
 impl SimpleState for GameState {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
-        let mut transform = Transform::default();
-        transform.set_scale(Vector3::new(2.0, 2.0, 2.0));
+        // Le LoadingState a verifie que tous les assets requis sont presents
+        let mut level = data.world.read_resource::<Levels>().0[self.level].clone();
 
-        data.world
-            .create_entity()
-            .with(self.scene.clone())
-            .with(transform)
-            .build();
+        // Une salle generee se rejoue a l'identique depuis une sauvegarde
+        let pages = level.objective.pages.max(GENERATED_PAGES);
+        let layout = level.generator.as_mut().map(|generator| {
+            let seed = self
+                .save
+                .as_ref()
+                .and_then(|save| save.seed)
+                .or(generator.seed)
+                .unwrap_or_else(rand::random);
+            generator.seed = Some(seed);
+            generate(seed, generator, pages)
+        });
+        if let Some(layout) = &layout {
+            layout.apply(&mut level);
+        }
 
+        let (scene, font, bashar, vignette, afit) = {
+            let assets = data.world.read_resource::<GameAssets>();
+            (
+                assets.scene(&level.scene),
+                assets.font("hud").expect("iléou le crow.ttf D:"),
+                assets.texture("bashar").expect("iléou bashar.jpeg"),
+                assets.texture("vignette").expect("iléou vignette.png"),
+                assets.sprite_sheet("afit").expect("iléou afit.png"),
+            )
+        };
+
+        let walls = Walls::from_zones(&level.walkable_zones());
+        let root = match (&layout, scene) {
+            (Some(layout), _) => layout.build(data.world, &walls),
+            (None, scene) => data
+                .world
+                .create_entity()
+                .with(scene.expect("iléou le SalleMachine.glb :("))
+                .with(scene_transform())
+                .build(),
+        };
+        data.world.insert(SceneRoot(Some(root)));
+
+        data.world.insert(walls);
+        data.world.insert(level.clone());
         data.world.insert(Afit::default());
         data.world.insert(UnlockedComputers::default());
         data.world.insert(TimeToScreamer::default());
-        data.world.insert(Reading(true));
-        data.world.insert(Sounds {
-            screamer: Some(self.screamer.clone()),
-            coming: Some(self.coming.clone()),
-            boot: Some(self.boot.clone()),
-        });
+        data.world.insert(GamePhase::Intro);
+        data.world.insert(load_stats());
+        data.world.insert(load_hud_scale());
+        data.world.insert(Look::default());
+        data.world.insert(CurrentRun::default());
+        data.world.insert(Panic::default());
+
+        self.caught_at = None;
+        self.events = Some(
+            data.world
+                .write_resource::<EventChannel<GameEvent>>()
+                .register_reader(),
+        );
 
         let bashar = data
             .world
             .create_entity()
-            .with(UiTransform::new(
-                "bashar".to_string(),
-                Anchor::Middle,
-                Anchor::Middle,
-                0.,
-                0.,
-                0.,
-                0.,
-                0.,
-            ))
-            .with(self.bashar.clone())
+            .with(
+                UiTransform::new(
+                    "bashar".to_string(),
+                    Anchor::Middle,
+                    Anchor::Middle,
+                    0.,
+                    0.,
+                    10.,
+                    0.,
+                    0.,
+                )
+                .with_stretch(Stretch::XY {
+                    x_margin: 0.,
+                    y_margin: 0.,
+                    keep_aspect_ratio: false,
+                }),
+            )
+            .with(UiImage::Texture(bashar))
+            .with(HiddenPropagate::new())
             .build();
 
         data.world.insert(Screamer {
             bashar: Some(bashar),
         });
 
-        initialize_camera(data.world);
+        let light = initialize_player(data.world, level.start);
+        data.world.insert(light);
+        data.world.insert(Flashlight::default());
+        data.world.insert(Stamina::default());
+
+        if let Some(save) = self.save.take() {
+            restore_save(data.world, save);
+        }
+
+        let page = SpriteRender {
+            sprite_sheet: afit,
+            sprite_number: 0,
+        };
+        initialize_pages(data.world, &level.pages, &page);
 
-        let texts = ui::create_texts(data.world, &self.font);
+        let texts = ui::create_texts(data.world, &font, &UiImage::Texture(vignette), &page);
         data.world.insert(texts);
 
-        let entity = initialize_light(data.world);
-        data.world.insert(entity);
+        let minimap = ui::create_minimap(data.world, &level);
+        data.world.insert(minimap);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        write_stats(&data.world.read_resource::<Stats>());
+
+        // Le prochain niveau repart de zero
+        self.events = None;
+        data.world.delete_all();
+        data.world.insert(Texts::default());
+        data.world.insert(Minimap::default());
+        data.world.insert(SceneRoot::default());
+        data.world.insert(Screamer::default());
+        data.world.insert(GamePhase::Intro);
+        data.world.write_resource::<HideCursor>().hide = false;
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let now = data.world.read_resource::<Time>().absolute_time_seconds();
+        let mut won = false;
+        if let Some(reader) = self.events.as_mut() {
+            for event in data
+                .world
+                .read_resource::<EventChannel<GameEvent>>()
+                .read(reader)
+            {
+                match event {
+                    GameEvent::PlayerCaught => {
+                        self.caught_at = Some(now);
+                        *data.world.write_resource::<GamePhase>() = GamePhase::Caught;
+                    }
+                    GameEvent::GameWon => {
+                        won = true;
+                        *data.world.write_resource::<GamePhase>() = GamePhase::Won;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if won {
+            delete_game(self.level);
+            let mut stats = data.world.write_resource::<Stats>();
+            stats.unlocked_levels = stats.unlocked_levels.max(self.level + 1);
+            return Trans::Switch(Box::new(LevelSelectState::finished(self.level)));
+        }
+
+        match self.caught_at {
+            Some(caught_at) if now - caught_at > CAUGHT_DELAY => Trans::Quit,
+            _ => Trans::None,
+        }
     }
 
     fn handle_event(
@@ -129,48 +441,126 @@ impl SimpleState for GameState {
     ) -> SimpleTrans {
         let StateData { world, .. } = data;
         if let StateEvent::Window(event) = &event {
+            let now = world.read_resource::<Time>().absolute_time_seconds();
+            let mut phase = world.write_resource::<GamePhase>();
+
+            // Echap met en pause la partie et libere la souris, un clic la recapture
             if is_key_down(&event, VirtualKeyCode::Escape) {
-                let mut hide_cursor = world.write_resource::<HideCursor>();
-                hide_cursor.hide = false;
+                world.write_resource::<HideCursor>().hide = false;
+                if *phase == GamePhase::Playing {
+                    *phase = GamePhase::Paused;
+                    self.paused_at = Some(now);
+                }
             } else if is_mouse_button_down(&event, MouseButton::Left) {
-                let mut hide_cursor = world.write_resource::<HideCursor>();
-                hide_cursor.hide = true;
+                world.write_resource::<HideCursor>().hide = true;
+                if *phase == GamePhase::Intro {
+                    *phase = GamePhase::Playing;
+                    start_run(world);
+                } else if let Some(paused_at) = self.paused_at.take() {
+                    *phase = GamePhase::Playing;
+
+                    // Bashar, les ordinateurs et le chronometre ont attendu eux aussi
+                    let shift = now - paused_at;
+                    world.write_resource::<TimeToScreamer>().shift(shift);
+                    world.write_resource::<UnlockedComputers>().shift(shift);
+                    world.write_resource::<CurrentRun>().started_at += shift;
+                }
             }
         }
         Trans::None
     }
 }
 
-fn initialize_light(world: &mut World) -> PlayerLight {
-    let light: Light = PointLight {
-        color: Rgb::new(1.0, 1.0, 1.0),
-        intensity: 2.0,
-        smoothness: 1.0,
-        ..PointLight::default()
+/// Ferme le texte d'introduction, la partie commence
+fn start_run(world: &World) {
+    let time = world.read_resource::<Time>();
+    world.write_resource::<CurrentRun>().started_at = time.absolute_time_seconds();
+    world.write_resource::<UnlockedComputers>().last_unlock_time = time.absolute_time();
+    world.write_resource::<GraphicsMenu>().shown = false;
+
+    if let Some(menu) = world.read_resource::<Texts>().menu {
+        if let Some(text) = world.write_storage::<UiText>().get_mut(menu) {
+            text.text = String::new();
+        }
     }
-    .into();
+}
+
+fn restore_save(world: &mut World, mut save: SaveGame) {
+    save.rebase(world.read_resource::<Time>().absolute_time_seconds());
+    save.place_player(
+        &mut world.write_storage::<Transform>(),
+        &world.read_storage::<Player>(),
+    );
+
+    world.insert(save.afit);
+    world.insert(save.unlocked_computers);
+    world.insert(save.time_to_screamer);
+    world.insert(save.player_hidden);
+}
+
+fn initialize_player(world: &mut World, (x, z): (f32, f32)) -> PlayerLight {
+    world.register::<Player>();
 
     let mut transform = Transform::default();
-    transform.set_translation_xyz(0.0, 1.5, 0.0);
+    transform.set_translation_xyz(x, 0.0, z);
 
-    let entity = world
+    let player = world.create_entity().with(Player).with(transform).build();
+
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(0.0, CAMERA_HEIGHT, 0.0);
+
+    let (width, height) = {
+        let screen = world.read_resource::<ScreenDimensions>();
+        (screen.width(), screen.height())
+    };
+
+    let camera = world
         .create_entity()
-        .with(light)
+        .with(Camera::standard_3d(width, height))
         .with(transform)
         .with(FlyControlTag::default())
+        .with(Parent::new(player))
         .build();
 
-    PlayerLight(Some(entity))
-}
-
-fn initialize_camera(world: &mut World) {
-    let mut transform = Transform::default();
-    transform.set_translation_xyz(0.0, 1.2, 0.0);
+    // Eteinte au depart, la FlashlightSystem l'oriente comme la camera
+    let light: Light = SpotLight {
+        angle: 0.5,
+        color: Rgb::new(1.0, 0.95, 0.85),
+        intensity: 0.0,
+        range: 12.0,
+        smoothness: 0.6,
+        ..SpotLight::default()
+    }
+    .into();
 
-    world
+    let light = world
         .create_entity()
-        .with(Camera::standard_3d(1024.0, 768.0))
-        .with(transform)
-        .with(FlyControlTag::default())
+        .with(light)
+        .with(Transform::default())
+        .with(Parent::new(camera))
         .build();
+
+    PlayerLight(Some(light))
+}
+
+// Le sprite fait 1920x1080 pixels, soit environ 40x20 cm
+const PAGE_SCALE: f32 = 0.0002;
+pub const PAGE_HEIGHT: f32 = 0.8;
+
+/// Les pages deja ramassees sont cachees par le PageSystem
+fn initialize_pages(world: &mut World, spots: &[(f32, f32)], page: &SpriteRender) {
+    world.register::<AfitPage>();
+
+    for (id, &(x, z)) in spots.iter().enumerate() {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(x, PAGE_HEIGHT, z);
+        transform.set_scale(Vector3::new(PAGE_SCALE, PAGE_SCALE, PAGE_SCALE));
+
+        world
+            .create_entity()
+            .with(page.clone())
+            .with(transform)
+            .with(AfitPage { id })
+            .build();
+    }
 }
@@ -0,0 +1,117 @@
+use amethyst::{
+    ecs::Entity,
+    input::{is_close_requested, is_key_down, VirtualKeyCode},
+    prelude::*,
+    ui::UiText,
+};
+
+use super::game::GameState;
+use crate::{
+    assets::GameAssets,
+    level::Levels,
+    save::{load_game, SaveGame},
+    stats::load_stats,
+    ui::{create_level_select, level_select_text},
+};
+
+/// Liste des niveaux, entre le chargement et chaque partie
+#[derive(Default)]
+pub struct LevelSelectState {
+    selected: usize,
+    /// Dernier niveau jouable
+    unlocked: usize,
+    /// Niveau qui vient d'etre termine
+    finished: Option<usize>,
+    save: Option<SaveGame>,
+    text: Option<Entity>,
+}
+
+impl LevelSelectState {
+    pub fn finished(level: usize) -> Self {
+        LevelSelectState {
+            finished: Some(level),
+            ..Default::default()
+        }
+    }
+
+    fn refresh(&self, world: &mut World) {
+        let text = level_select_text(
+            &world.read_resource::<Levels>(),
+            self.selected,
+            self.unlocked,
+            self.finished,
+            self.save.as_ref().map(|save| save.level),
+        );
+
+        if let Some(entity) = self.text {
+            if let Some(ui) = world.write_storage::<UiText>().get_mut(entity) {
+                ui.text = text;
+            }
+        }
+    }
+}
+
+impl SimpleState for LevelSelectState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let count = data.world.read_resource::<Levels>().0.len();
+        self.unlocked = load_stats().unlocked_levels.min(count - 1);
+        // Une sauvegarde a l'objectif deja rempli ne ferait que rejouer la fuite
+        self.save = load_game().filter(|save| {
+            match data.world.read_resource::<Levels>().0.get(save.level) {
+                Some(level) => !save.afit.is_complete(&level.objective),
+                None => false,
+            }
+        });
+
+        // On propose le niveau suivant, sinon celui de la sauvegarde
+        self.selected = match (self.finished, &self.save) {
+            (Some(finished), _) => (finished + 1).min(self.unlocked),
+            (None, Some(save)) => save.level.min(self.unlocked),
+            (None, None) => self.unlocked,
+        };
+
+        let font = data
+            .world
+            .read_resource::<GameAssets>()
+            .font("hud")
+            .expect("iléou le crow.ttf D:");
+        self.text = Some(create_level_select(data.world, font, String::new()));
+        self.refresh(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(text) = self.text.take() {
+            data.world
+                .delete_entity(text)
+                .expect("Failed to delete the level select");
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(event) || is_key_down(event, VirtualKeyCode::Escape) {
+                return Trans::Quit;
+            }
+
+            if is_key_down(event, VirtualKeyCode::Return) {
+                let level = self.selected;
+                let save = self.save.take().filter(|save| save.level == level);
+                return Trans::Switch(Box::new(GameState::new(level, save)));
+            }
+
+            // Les niveaux verrouilles ne se selectionnent pas
+            if is_key_down(event, VirtualKeyCode::Up) && self.selected > 0 {
+                self.selected -= 1;
+                self.refresh(data.world);
+            } else if is_key_down(event, VirtualKeyCode::Down) && self.selected < self.unlocked {
+                self.selected += 1;
+                self.refresh(data.world);
+            }
+        }
+        Trans::None
+    }
+}
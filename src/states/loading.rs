@@ -1,113 +1,105 @@
 use amethyst::{
-    assets::{AssetStorage, Handle, Loader, ProgressCounter},
-    audio::{Mp3Format, SourceHandle},
-    ecs::World,
+    assets::{Completion, Loader, ProgressCounter},
     prelude::*,
-    renderer::{ImageFormat, SpriteRender, SpriteSheet, SpriteSheetFormat, Texture},
-    ui::{FontHandle, TtfFormat, UiImage},
+    ui::{UiText, UiTransform},
 };
-use amethyst_gltf::{GltfSceneAsset, GltfSceneFormat};
 
-use super::game::GameState;
+use super::{error::ErrorState, level_select::LevelSelectState};
+use crate::{
+    assets::{load_manifest, GameAssets},
+    level::load_levels,
+    ui::{create_loading_screen, LoadingScreen, LOADING_BAR_WIDTH},
+    DevMode,
+};
 
+#[derive(Default)]
 pub struct LoadingState {
     pub progress_counter: ProgressCounter,
-    pub scene: Option<Handle<GltfSceneAsset>>,
-    pub screamer: Option<SourceHandle>,
-    pub coming: Option<SourceHandle>,
-    pub boot: Option<SourceHandle>,
-    pub font: Option<FontHandle>,
-    pub afit: Option<Handle<SpriteSheet>>,
-    pub bashar: Option<Handle<Texture>>,
+    pub assets: Option<GameAssets>,
+    /// Erreurs detectees avant meme de charger les assets
+    pub errors: Vec<(String, String)>,
+    pub screen: Option<LoadingScreen>,
 }
 
-impl LoadingState {
-    fn load_sprite_sheet(&mut self, world: &World) -> Handle<SpriteSheet> {
-        let texture_handle = {
-            let loader = world.read_resource::<Loader>();
-            let texture_storage = world.read_resource::<AssetStorage<Texture>>();
-            loader.load(
-                "textures/afit.png",
-                ImageFormat::default(),
-                &mut self.progress_counter,
-                &texture_storage,
-            )
-        };
+impl SimpleState for LoadingState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        // Sans `--dev` le loader n'a pas besoin de garder de quoi recharger les assets
+        let dev = data.world.read_resource::<DevMode>().0;
+        data.world.write_resource::<Loader>().set_hot_reload(dev);
 
-        let loader = world.read_resource::<Loader>();
-        let sprite_sheet_store = world.read_resource::<AssetStorage<SpriteSheet>>();
+        match load_manifest() {
+            Ok(manifest) => {
+                for name in manifest.missing() {
+                    self.errors
+                        .push((name, "absent de assets/manifest.ron".to_string()));
+                }
 
-        loader.load(
-            "textures/afit_spritesheet.ron", // Here we load the associated ron file
-            SpriteSheetFormat(texture_handle),
-            &mut self.progress_counter,
-            &sprite_sheet_store,
-        )
-    }
-}
+                match load_levels() {
+                    Ok(levels) => {
+                        for level in &levels.0 {
+                            if level.generator.is_none()
+                                && !manifest.scenes.contains_key(&level.scene)
+                            {
+                                self.errors.push((
+                                    format!("scenes.{}", level.scene),
+                                    format!("absent de assets/manifest.ron ({})", level.name),
+                                ));
+                            }
+                        }
+                        data.world.insert(levels);
+                    }
+                    Err(e) => self.errors.push(("assets/levels.ron".to_string(), e)),
+                }
 
-impl SimpleState for LoadingState {
-    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
-        let loader = data.world.read_resource::<Loader>();
+                self.assets = Some(manifest.load_assets(data.world, &mut self.progress_counter));
+            }
+            Err(e) => self.errors.push(("assets/manifest.ron".to_string(), e)),
+        }
 
-        self.scene = Some(loader.load(
-            "models/SalleMachine.glb",
-            GltfSceneFormat::default(),
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
-        self.screamer = Some(loader.load(
-            "sounds/screamer.mp3",
-            Mp3Format,
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
-        self.coming = Some(loader.load(
-            "sounds/coming.mp3",
-            Mp3Format,
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
-        self.boot = Some(loader.load(
-            "sounds/boot.mp3",
-            Mp3Format,
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
-        self.font = Some(loader.load(
-            "fonts/crow.ttf",
-            TtfFormat,
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
-        self.afit = Some(self.load_sprite_sheet(&data.world));
-        self.bashar = Some(loader.load(
-            "textures/bashar.jpeg",
-            ImageFormat::default(),
-            &mut self.progress_counter,
-            &data.world.read_resource(),
-        ));
+        self.screen = Some(create_loading_screen(data.world));
     }
 
-    fn update(&mut self, _data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        if self.progress_counter.is_complete() {
-            Trans::Switch(Box::new(GameState {
-                scene: self.scene.take().expect(
-                    "Expected `scene` to exist when \
-                        `progress_counter` is complete.",
-                ),
-                screamer: self.screamer.take().expect("iléou le screamer.mp3 :("),
-                coming: self.coming.take().expect("iléou le coming.mp3 :c"),
-                boot: self.boot.take().expect("iléou le boot.mp3 :<"),
-                font: self.font.take().expect("iléou le crow.ttf D:"),
-                afit: SpriteRender {
-                    sprite_sheet: self.afit.take().expect("iléou le afit.png"),
-                    sprite_number: 0,
-                },
-                bashar: UiImage::Texture(self.bashar.take().expect("iléou bashar.jpeg")),
-            }))
-        } else {
-            Trans::None
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(screen) = self.screen.take() {
+            screen.delete(data.world);
         }
     }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let counter = &self.progress_counter;
+        if let Some(screen) = &self.screen {
+            let total = counter.num_assets().max(1);
+            let done = counter.num_finished() + counter.num_failed();
+
+            if let Some(bar) = data
+                .world
+                .write_storage::<UiTransform>()
+                .get_mut(screen.bar)
+            {
+                bar.width = LOADING_BAR_WIDTH * done as f32 / total as f32;
+            }
+            if let Some(text) = data.world.write_storage::<UiText>().get_mut(screen.text) {
+                text.text = format!("Chargement... {}/{}", counter.num_finished(), total);
+            }
+        }
+
+        if counter.num_loading() > 0 {
+            return Trans::None;
+        }
+
+        // On attend la fin du chargement pour lister toutes les erreurs d'un coup
+        if counter.complete() == Completion::Failed || !self.errors.is_empty() {
+            let mut errors = std::mem::take(&mut self.errors);
+            errors.extend(
+                counter
+                    .errors()
+                    .into_iter()
+                    .map(|error| (error.asset_name, error.error.to_string())),
+            );
+            return Trans::Switch(Box::new(ErrorState { errors }));
+        }
+
+        data.world.insert(self.assets.take().unwrap_or_default());
+        Trans::Switch(Box::new(LevelSelectState::default()))
+    }
 }
@@ -1,2 +1,4 @@
+pub mod error;
 pub mod game;
+pub mod level_select;
 pub mod loading;
@@ -0,0 +1,57 @@
+use amethyst::config::Config;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::save::data_path;
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// Meilleur temps (en secondes) pour atteindre 100% des tests
+    pub best_completion_time: Option<f64>,
+    pub most_code_found: u8,
+    pub total_deaths: u32,
+    pub times_hidden: u32,
+    /// Somme des temps de survie des parties perdues
+    pub total_survival_time: f64,
+    /// Nombre de niveaux termines, le suivant est jouable
+    #[serde(default)]
+    pub unlocked_levels: usize,
+}
+
+impl Stats {
+    pub fn average_survival_time(&self) -> Option<f64> {
+        if self.total_deaths == 0 {
+            None
+        } else {
+            Some(self.total_survival_time / self.total_deaths as f64)
+        }
+    }
+}
+
+/// Partie en cours, pour calculer les temps des statistiques
+#[derive(Default)]
+pub struct CurrentRun {
+    pub started_at: f64,
+    /// Objectif du niveau rempli
+    pub completed: bool,
+}
+
+pub fn load_stats() -> Stats {
+    let path = match data_path("stats.ron") {
+        Some(path) if path.exists() => path,
+        _ => return Stats::default(),
+    };
+
+    Stats::load(&path).unwrap_or_else(|e| {
+        warn!("Failed to load stats {}: {}", path.display(), e);
+        Stats::default()
+    })
+}
+
+pub fn write_stats(stats: &Stats) {
+    if let Some(path) = data_path("stats.ron") {
+        if let Err(e) = stats.write(&path) {
+            warn!("Failed to write stats {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use amethyst::{
+    assets::AssetStorage,
+    audio::{output::Output, Source},
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{assets::GameAssets, play, states::game::GameEvent};
+
+/// Joue les sons qui accompagnent les evenements de la partie
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(AudioCueSystemDesc))]
+pub struct AudioCueSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<GameEvent>,
+}
+
+impl AudioCueSystem {
+    pub fn new(event_reader: ReaderId<GameEvent>) -> Self {
+        AudioCueSystem { event_reader }
+    }
+}
+
+impl<'s> System<'s> for AudioCueSystem {
+    type SystemData = (
+        Read<'s, AssetStorage<Source>>,
+        Read<'s, GameAssets>,
+        Option<Read<'s, Output>>,
+        Read<'s, EventChannel<GameEvent>>,
+    );
+
+    fn run(&mut self, (storage, assets, output, events): Self::SystemData) {
+        for event in events.read(&mut self.event_reader) {
+            let (sound, volume) = match event {
+                GameEvent::ComputerUnlocked(_) => ("boot", 0.2),
+                GameEvent::BasharComing => ("coming", 0.65),
+                GameEvent::PlayerCaught => ("screamer", 0.9),
+                _ => continue,
+            };
+            play(&storage, &assets.sound(sound), &output, volume);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use amethyst::{
+    core::{
+        math::{UnitQuaternion, Vector2, Vector3},
+        Time, Transform,
+    },
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    renderer::Camera,
+    shrev::{EventChannel, ReaderId},
+};
+use std::f32::consts::PI;
+
+use crate::{
+    settings::Settings,
+    states::game::{GameEvent, Player, PlayerHidden},
+};
+
+// Un balancement complet tous les deux pas
+const BOB_FREQUENCY: f32 = PI / 0.8;
+const BOB_HEIGHT: f32 = 0.035;
+const BOB_SWAY: f32 = 0.02;
+// Vitesse a partir de laquelle le balancement est complet
+const BOB_FULL_SPEED: f32 = 2.5;
+const BOB_BLEND: f32 = 6.0;
+// Tremblement (en radians) et duree quand Bashar arrive
+const COMING_SHAKE: f32 = 0.012;
+const COMING_DURATION: f64 = 2.5;
+// Sursaut (en radians) et duree quand Bashar nous attrape
+const CAUGHT_JOLT: f32 = 0.15;
+const CAUGHT_DURATION: f64 = 0.6;
+
+/// Ajoute balancement et tremblements a la camera, apres que sa position et son regard ont ete fixes
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(CameraEffectsSystemDesc))]
+pub struct CameraEffectsSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<GameEvent>,
+    #[system_desc(skip)]
+    bob_phase: f32,
+    #[system_desc(skip)]
+    bob_amount: f32,
+    #[system_desc(skip)]
+    last_position: Option<Vector2<f32>>,
+    #[system_desc(skip)]
+    coming_at: Option<f64>,
+    #[system_desc(skip)]
+    caught_at: Option<f64>,
+}
+
+impl CameraEffectsSystem {
+    pub fn new(event_reader: ReaderId<GameEvent>) -> Self {
+        CameraEffectsSystem {
+            event_reader,
+            bob_phase: 0.0,
+            bob_amount: 0.0,
+            last_position: None,
+            coming_at: None,
+            caught_at: None,
+        }
+    }
+}
+
+impl<'s> System<'s> for CameraEffectsSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, Settings>,
+        Read<'s, EventChannel<GameEvent>>,
+        Read<'s, PlayerHidden>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Camera>,
+    );
+
+    fn run(
+        &mut self,
+        (time, settings, events, hidden, mut transforms, players, cameras): Self::SystemData,
+    ) {
+        let now = time.absolute_time_seconds();
+        let delta = time.delta_seconds();
+
+        for event in events.read(&mut self.event_reader) {
+            match event {
+                GameEvent::BasharComing => self.coming_at = Some(now),
+                GameEvent::PlayerCaught => self.caught_at = Some(now),
+                _ => {}
+            }
+        }
+
+        let position = (&transforms, &players).join().next().map(|(transform, _)| {
+            let pos = transform.translation();
+            Vector2::new(pos.x, pos.z)
+        });
+
+        // On ne marche pas en se glissant sous un bureau
+        let walked = match (self.last_position, position) {
+            (Some(last), Some(current)) if !hidden.is_busy() => (current - last).norm(),
+            _ => 0.0,
+        };
+        self.last_position = position;
+
+        let speed = if delta > 0.0 { walked / delta } else { 0.0 };
+        let blend = 1.0 - (-delta * BOB_BLEND).exp();
+        self.bob_amount += ((speed / BOB_FULL_SPEED).min(1.0) - self.bob_amount) * blend;
+        self.bob_phase = (self.bob_phase + walked * BOB_FREQUENCY) % (2.0 * PI);
+
+        let intensity = settings.camera_effects.factor();
+        if intensity <= 0.0 {
+            return;
+        }
+
+        let bob = Vector3::new(
+            self.bob_phase.sin() * BOB_SWAY,
+            (2.0 * self.bob_phase).sin() * BOB_HEIGHT,
+            0.0,
+        ) * self.bob_amount;
+
+        let mut shake = 0.0;
+        if let Some(at) = self.coming_at {
+            let elapsed = now - at;
+            if elapsed < COMING_DURATION {
+                shake += COMING_SHAKE * (1.0 - (elapsed / COMING_DURATION) as f32);
+            } else {
+                self.coming_at = None;
+            }
+        }
+
+        let mut jolt = 0.0;
+        if let Some(at) = self.caught_at {
+            let elapsed = now - at;
+            if elapsed < CAUGHT_DURATION {
+                let left = 1.0 - (elapsed / CAUGHT_DURATION) as f32;
+                jolt = CAUGHT_JOLT * left * left;
+            } else {
+                self.caught_at = None;
+            }
+        }
+
+        // Des sinus de frequences differentes pour un tremblement qui ne boucle pas
+        let t = now as f32;
+        let rotation = UnitQuaternion::from_euler_angles(
+            shake * (t * 31.0).sin() + jolt * (1.0 + 0.5 * (t * 47.0).sin()),
+            shake * (t * 23.0).sin() + jolt * 0.6 * (t * 53.0).sin(),
+            shake * 0.5 * (t * 17.0).sin() + jolt * 0.8 * (t * 41.0).sin(),
+        );
+
+        for (transform, _) in (&mut transforms, &cameras).join() {
+            let translation = transform.translation() + bob * intensity;
+            transform.set_translation(translation);
+
+            let rotation =
+                transform.rotation() * UnitQuaternion::identity().slerp(&rotation, intensity);
+            transform.set_rotation(rotation);
+        }
+    }
+}
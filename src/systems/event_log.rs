@@ -0,0 +1,84 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Entity, Read, System, SystemData, WriteStorage},
+    shrev::{EventChannel, ReaderId},
+    ui::UiText,
+};
+use std::collections::VecDeque;
+
+use crate::{
+    level::Level,
+    states::game::GameEvent,
+    ui::{event_text, Texts, LOG_LINES},
+};
+
+// Duree (en secondes) d'affichage d'une ligne, dont le fondu
+const LOG_DURATION: f64 = 6.0;
+const LOG_FADE: f64 = 1.5;
+
+/// Journal des derniers evenements, qui s'effacent avec le temps
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(EventLogSystemDesc))]
+pub struct EventLogSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<GameEvent>,
+    /// (texte, temps d'apparition), le plus recent en premier
+    #[system_desc(skip)]
+    entries: VecDeque<(String, f64)>,
+    /// Lignes du HUD ou s'affiche le journal, recreees a chaque niveau
+    #[system_desc(skip)]
+    lines: Vec<Entity>,
+}
+
+impl EventLogSystem {
+    pub fn new(event_reader: ReaderId<GameEvent>) -> Self {
+        EventLogSystem {
+            event_reader,
+            entries: VecDeque::new(),
+            lines: Vec::new(),
+        }
+    }
+}
+
+impl<'s> System<'s> for EventLogSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, EventChannel<GameEvent>>,
+        Read<'s, Level>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Texts>,
+    );
+
+    fn run(&mut self, (time, events, level, mut ui, texts): Self::SystemData) {
+        let now = time.absolute_time_seconds();
+
+        // Un nouveau niveau repart avec un journal vide
+        if self.lines != texts.log {
+            self.lines = texts.log.clone();
+            self.entries.clear();
+        }
+
+        for event in events.read(&mut self.event_reader) {
+            if let Some(text) = event_text(event, &level) {
+                self.entries.push_front((text, now));
+            }
+        }
+        self.entries.truncate(LOG_LINES);
+        self.entries
+            .retain(|(_, shown_at)| now - shown_at < LOG_DURATION);
+
+        for (i, line) in texts.log.iter().enumerate() {
+            if let Some(text) = ui.get_mut(*line) {
+                match self.entries.get(i) {
+                    Some((entry, shown_at)) => {
+                        let left = LOG_DURATION - (now - shown_at);
+                        text.text = entry.clone();
+                        text.color[3] = (left / LOG_FADE).min(1.0) as f32;
+                    }
+                    None => text.color[3] = 0.0,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use amethyst::{
+    core::{math::Vector3, Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    input::{InputHandler, StringBindings},
+    renderer::{light::Light, Camera},
+};
+
+use crate::{
+    states::game::{Flashlight, GamePhase, Player, PlayerLight},
+    systems::phase::PhaseSystem,
+};
+
+const FLASHLIGHT_INTENSITY: f32 = 4.0;
+// Duree (en secondes) d'une batterie pleine, et d'une recharge complete lampe eteinte
+const BATTERY_LIFE: f32 = 90.0;
+const BATTERY_RECHARGE: f32 = 240.0;
+// En dessous de ce niveau la lampe clignote
+const BATTERY_LOW: f32 = 0.2;
+
+/// Allume et eteint la lampe, et use sa batterie pendant la partie
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(BatterySystemDesc))]
+pub struct BatterySystem;
+
+impl PhaseSystem for BatterySystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
+impl<'s> System<'s> for BatterySystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, InputHandler<StringBindings>>,
+        Write<'s, Flashlight>,
+    );
+
+    fn run(&mut self, (time, input, mut flashlight): Self::SystemData) {
+        if let Some(pressed) = input.action_is_down("flashlight") {
+            if pressed && !flashlight.pressed {
+                flashlight.on = !flashlight.on && flashlight.battery > 0.0;
+            }
+            flashlight.pressed = pressed;
+        }
+
+        if flashlight.on {
+            flashlight.battery =
+                (flashlight.battery - time.delta_seconds() / BATTERY_LIFE).max(0.0);
+            if flashlight.battery <= 0.0 {
+                flashlight.on = false;
+            }
+        } else {
+            flashlight.battery =
+                (flashlight.battery + time.delta_seconds() / BATTERY_RECHARGE).min(1.0);
+        }
+    }
+}
+
+/// Oriente le spot de la lampe avec le regard, et le fait clignoter batterie faible
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(FlashlightSystemDesc))]
+pub struct FlashlightSystem;
+
+impl<'s> System<'s> for FlashlightSystem {
+    type SystemData = (
+        Read<'s, Flashlight>,
+        Read<'s, PlayerLight>,
+        WriteStorage<'s, Light>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Player>,
+    );
+
+    fn run(
+        &mut self,
+        (flashlight, player_light, mut lights, transforms, cameras, players): Self::SystemData,
+    ) {
+        let light = match player_light.0 {
+            Some(light) => light,
+            None => return,
+        };
+
+        // La lampe est attachee a la camera, mais la direction d'un spot n'est pas relative
+        let player = (&transforms, &players).join().next();
+        let camera = (&transforms, &cameras).join().next();
+
+        if let (Some((player, _)), Some((camera, _))) = (player, camera) {
+            let direction = player.rotation() * camera.rotation() * -Vector3::z();
+
+            if let Some(Light::Spot(spot)) = lights.get_mut(light) {
+                spot.direction = direction;
+                spot.intensity = if !flashlight.on {
+                    0.0
+                } else if flashlight.battery < BATTERY_LOW && rand::random::<f32>() < 0.15 {
+                    FLASHLIGHT_INTENSITY * rand::random::<f32>() * 0.3
+                } else {
+                    FLASHLIGHT_INTENSITY
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use amethyst::{
+    core::frame_limiter::{FrameLimiter, FrameRateLimitStrategy},
+    derive::SystemDesc,
+    ecs::{Join, Read, System, SystemData, Write, WriteStorage},
+    renderer::{palette::Srgba, resources::AmbientColor, Camera},
+    window::{ScreenDimensions, Window},
+    winit::dpi::LogicalSize,
+};
+
+use crate::settings::Settings;
+
+// Lumiere ambiante pour une luminosite de 100%
+const BASE_AMBIENT: f32 = 0.03;
+// Images par seconde avec la synchronisation verticale
+const VSYNC_FPS: u32 = 60;
+
+/// Applique les reglages graphiques des qu'ils changent
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(GraphicsSystemDesc))]
+pub struct GraphicsSystem {
+    applied: Option<Settings>,
+}
+
+impl<'s> System<'s> for GraphicsSystem {
+    type SystemData = (
+        Read<'s, Settings>,
+        Option<Read<'s, Window>>,
+        Option<Read<'s, ScreenDimensions>>,
+        Write<'s, FrameLimiter>,
+        Write<'s, AmbientColor>,
+        WriteStorage<'s, Camera>,
+    );
+
+    fn run(
+        &mut self,
+        (settings, window, screen, mut limiter, mut ambient, mut cameras): Self::SystemData,
+    ) {
+        let previous = self.applied.replace(settings.clone());
+        let (fullscreen_changed, resolution_changed, vsync_changed) = match previous {
+            Some(previous) => (
+                previous.fullscreen != settings.fullscreen,
+                // En sortant du plein ecran il faut remettre la bonne taille
+                previous.resolution != settings.resolution
+                    || previous.fullscreen != settings.fullscreen,
+                previous.vsync != settings.vsync,
+            ),
+            None => (true, true, true),
+        };
+
+        if let Some(window) = &window {
+            if fullscreen_changed {
+                window.set_fullscreen(if settings.fullscreen {
+                    Some(window.get_current_monitor())
+                } else {
+                    None
+                });
+            }
+
+            if resolution_changed && !settings.fullscreen {
+                let (width, height) = settings.resolution;
+                window.set_inner_size(LogicalSize::new(width as f64, height as f64));
+            }
+        }
+
+        // amethyst choisit lui meme le mode de presentation, on ne peut que limiter les images
+        if vsync_changed {
+            if settings.vsync {
+                limiter.set_rate(FrameRateLimitStrategy::Yield, VSYNC_FPS);
+            } else {
+                limiter.set_rate(FrameRateLimitStrategy::Unlimited, 0);
+            }
+        }
+
+        let level = BASE_AMBIENT * settings.brightness;
+        ambient.0 = Srgba::new(level, level, level, 1.);
+
+        // La camera peut etre creee apres les reglages, on la met donc a jour a chaque image
+        if let Some(screen) = screen {
+            for camera in (&mut cameras).join() {
+                if let Some(perspective) = camera.projection_mut().as_perspective_mut() {
+                    perspective
+                        .set_fov_and_aspect(settings.fov.to_radians(), screen.aspect_ratio());
+                }
+            }
+        }
+    }
+}
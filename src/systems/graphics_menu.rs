@@ -0,0 +1,114 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write, WriteStorage},
+    input::{InputHandler, StringBindings},
+    ui::UiText,
+};
+
+use crate::{
+    settings::{
+        write_settings, Settings, MAX_BRIGHTNESS, MAX_FOV, MIN_BRIGHTNESS, MIN_FOV, RESOLUTIONS,
+    },
+    states::game::GamePhase,
+    systems::phase::PhaseSystem,
+    ui::{graphics_text, GraphicsMenu, MenuStats, Texts, GRAPHICS_ENTRIES, INTRO_TEXT},
+};
+
+const ACTIONS: [&str; 5] = [
+    "graphics",
+    "menu_up",
+    "menu_down",
+    "menu_left",
+    "menu_right",
+];
+
+/// Menu des graphismes, accessible depuis l'ecran d'introduction
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(GraphicsMenuSystemDesc))]
+pub struct GraphicsMenuSystem {
+    #[system_desc(skip)]
+    pressed: [bool; 5],
+}
+
+impl PhaseSystem for GraphicsMenuSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Intro];
+}
+
+impl<'s> System<'s> for GraphicsMenuSystem {
+    type SystemData = (
+        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, MenuStats>,
+        Write<'s, GraphicsMenu>,
+        Write<'s, Settings>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Texts>,
+    );
+
+    fn run(
+        &mut self,
+        (input, menu_stats, mut menu, mut settings, mut ui, texts): Self::SystemData,
+    ) {
+        let mut just_pressed = [false; 5];
+        for (i, action) in ACTIONS.iter().enumerate() {
+            let pressed = input.action_is_down(*action).unwrap_or(false);
+            just_pressed[i] = pressed && !self.pressed[i];
+            self.pressed[i] = pressed;
+        }
+        let [toggle, up, down, left, right] = just_pressed;
+
+        if menu_stats.shown {
+            return;
+        }
+
+        let mut refresh = false;
+        if toggle {
+            menu.shown = !menu.shown;
+            refresh = true;
+        }
+
+        if menu.shown {
+            if up {
+                menu.selected = (menu.selected + GRAPHICS_ENTRIES - 1) % GRAPHICS_ENTRIES;
+            }
+            if down {
+                menu.selected = (menu.selected + 1) % GRAPHICS_ENTRIES;
+            }
+            if left != right {
+                adjust(&mut settings, menu.selected, if right { 1 } else { -1 });
+                write_settings(&settings);
+            }
+            refresh |= up || down || left || right;
+        }
+
+        if refresh {
+            if let Some(text) = texts.menu.and_then(|menu| ui.get_mut(menu)) {
+                text.text = if menu.shown {
+                    graphics_text(&settings, menu.selected)
+                } else {
+                    INTRO_TEXT.to_string()
+                };
+            }
+        }
+    }
+}
+
+fn adjust(settings: &mut Settings, entry: usize, step: i32) {
+    match entry {
+        0 => settings.fullscreen = !settings.fullscreen,
+        1 => {
+            let current = RESOLUTIONS
+                .iter()
+                .position(|resolution| *resolution == settings.resolution)
+                .unwrap_or(0) as i32;
+            let count = RESOLUTIONS.len() as i32;
+            settings.resolution = RESOLUTIONS[((current + step + count) % count) as usize];
+        }
+        2 => settings.vsync = !settings.vsync,
+        3 => settings.fov = (settings.fov + 5. * step as f32).clamp(MIN_FOV, MAX_FOV),
+        4 => {
+            settings.brightness =
+                (settings.brightness + 0.1 * step as f32).clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS)
+        }
+        _ => settings.subtitles = !settings.subtitles,
+    }
+}
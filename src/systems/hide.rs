@@ -1,31 +1,67 @@
 use amethyst::{
+    core::{Time, Transform},
     derive::SystemDesc,
-    ecs::{Read, System, SystemData, Write, WriteStorage},
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
     input::{InputHandler, StringBindings},
-    renderer::light::Light,
+    renderer::Camera,
+    shrev::EventChannel,
     ui::UiText,
 };
 
 use crate::{
-    states::game::{PlayerHidden, PlayerLight},
+    level::Level,
+    states::game::{GameEvent, GamePhase, Panic, Player, PlayerHidden},
+    systems::phase::PhaseSystem,
     ui::Texts,
 };
 
+// Duree (en secondes) pour se glisser sous le bureau
+const HIDE_TRANSITION: f32 = 0.6;
+// Hauteur de la camera sous le bureau
+const HIDDEN_HEIGHT: f32 = 0.45;
+// Panique gagnee par seconde sous le bureau, et perdue par seconde debout
+const PANIC_RISE: f32 = 1.0 / 25.0;
+const PANIC_DECAY: f32 = 1.0 / 8.0;
+
 #[derive(Debug, SystemDesc)]
 #[system_desc(name(HidingSystemDesc))]
 pub struct HidingSystem;
 
+impl PhaseSystem for HidingSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
 impl<'s> System<'s> for HidingSystem {
     type SystemData = (
+        Read<'s, Time>,
         Write<'s, PlayerHidden>,
         WriteStorage<'s, UiText>,
         Read<'s, Texts>,
         Read<'s, InputHandler<StringBindings>>,
-        WriteStorage<'s, Light>,
-        Read<'s, PlayerLight>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Player>,
+        Write<'s, Panic>,
+        Read<'s, Level>,
+        Write<'s, EventChannel<GameEvent>>,
     );
 
-    fn run(&mut self, (mut hidden, mut ui, texts, bindings, mut lights, light): Self::SystemData) {
+    fn run(
+        &mut self,
+        (
+            time,
+            mut hidden,
+            mut ui,
+            texts,
+            bindings,
+            mut transforms,
+            cameras,
+            players,
+            mut panic,
+            level,
+            mut events,
+        ): Self::SystemData,
+    ) {
         if let Some(hide) = texts.hide {
             if let Some(text) = ui.get_mut(hide) {
                 if hidden.hidden {
@@ -39,9 +75,28 @@ impl<'s> System<'s> for HidingSystem {
         }
 
         if let Some(pressed) = bindings.action_is_down("hide") {
-            if pressed && !hidden.pressed {
+            if pressed && !hidden.pressed && (hidden.hidden || hidden.can_hide) {
                 hidden.pressed = true;
                 hidden.hidden = !hidden.hidden;
+
+                if hidden.hidden {
+                    events.single_write(GameEvent::PlayerHid);
+
+                    // On ne change de cachette qu'en partant de debout
+                    if hidden.progress <= 0.0 {
+                        let player = (&transforms, &players).join().next();
+                        let camera = (&transforms, &cameras).join().next();
+                        if let (Some((player, _)), Some((camera, _))) = (player, camera) {
+                            let pos =
+                                player.translation() + player.rotation() * camera.translation();
+                            hidden.from = (pos.x, camera.translation().y, pos.z);
+                            // Sans bureau on reste sur place
+                            hidden.spot = level
+                                .nearest_desk(pos.x, pos.z)
+                                .unwrap_or((pos.x, pos.z, 0.0));
+                        }
+                    }
+                }
             }
 
             if !pressed && hidden.pressed {
@@ -49,11 +104,42 @@ impl<'s> System<'s> for HidingSystem {
             }
         }
 
-        if let Some(light) = light.0 {
-            if let Some(light) = lights.get_mut(light) {
-                if let Light::Point(point) = light {
-                    point.intensity = if hidden.hidden { 0.0 } else { 2.0 };
-                }
+        if hidden.is_safe() {
+            panic.value = (panic.value + time.delta_seconds() * PANIC_RISE).min(1.0);
+
+            // Le joueur craque et sort de sa cachette
+            if panic.value >= 1.0 {
+                hidden.hidden = false;
+            }
+        } else if !hidden.is_busy() {
+            panic.value = (panic.value - time.delta_seconds() * PANIC_DECAY).max(0.0);
+        }
+
+        let step = time.delta_seconds() / HIDE_TRANSITION;
+        let was_busy = hidden.is_busy();
+        hidden.progress = if hidden.hidden {
+            (hidden.progress + step).min(1.0)
+        } else {
+            (hidden.progress - step).max(0.0)
+        };
+
+        if was_busy {
+            let t = hidden.progress * hidden.progress * (3.0 - 2.0 * hidden.progress);
+            let (from_x, from_y, from_z) = hidden.from;
+            let (spot_x, spot_z, _) = hidden.spot;
+
+            // Le penchement est integre a la position du joueur pendant la transition
+            for (transform, _) in (&mut transforms, &players).join() {
+                transform.set_translation_xyz(
+                    from_x + (spot_x - from_x) * t,
+                    0.0,
+                    from_z + (spot_z - from_z) * t,
+                );
+            }
+
+            // Le regard est limite par le MouseLookSystem
+            for (transform, _) in (&mut transforms, &cameras).join() {
+                transform.set_translation_xyz(0.0, from_y + (HIDDEN_HEIGHT - from_y) * t, 0.0);
             }
         }
     }
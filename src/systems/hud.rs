@@ -0,0 +1,69 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, Read, System, SystemData, Write, WriteStorage},
+    ui::{UiText, UiTransform},
+    window::ScreenDimensions,
+};
+
+use crate::ui::{HudLayout, HudScale};
+
+/// Redimensionne le HUD quand la taille de la fenetre change
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(HudScaleSystemDesc))]
+pub struct HudScaleSystem {
+    last_size: Option<(f32, f32)>,
+}
+
+impl<'s> System<'s> for HudScaleSystem {
+    type SystemData = (
+        Option<Read<'s, ScreenDimensions>>,
+        Write<'s, HudScale>,
+        WriteStorage<'s, HudLayout>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiText>,
+    );
+
+    fn run(&mut self, (screen, mut hud, mut layouts, mut transforms, mut texts): Self::SystemData) {
+        let screen = match screen {
+            Some(screen) => screen,
+            None => return,
+        };
+
+        // Les nouveaux elements gardent leurs valeurs de reference
+        let mut added = false;
+        for (layout, transform, text) in (&mut layouts, &transforms, texts.maybe()).join() {
+            if layout.base.is_none() {
+                layout.base = Some((
+                    transform.local_x,
+                    transform.local_y,
+                    transform.width,
+                    transform.height,
+                    text.map(|text| text.font_size),
+                ));
+                added = true;
+            }
+        }
+
+        let size = (screen.width(), screen.height());
+        if !added && self.last_size == Some(size) {
+            return;
+        }
+        self.last_size = Some(size);
+
+        let (width, height) = hud.reference;
+        hud.scale = (screen.width() / width).min(screen.height() / height);
+
+        for (layout, transform, text) in (&layouts, &mut transforms, (&mut texts).maybe()).join() {
+            if let Some((x, y, width, height, font_size)) = layout.base {
+                transform.local_x = x * hud.scale;
+                transform.local_y = y * hud.scale;
+                transform.width = width * hud.scale;
+                transform.height = height * hud.scale;
+
+                if let (Some(text), Some(font_size)) = (text, font_size) {
+                    text.font_size = font_size * hud.scale;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use amethyst::{
+    controls::{HideCursor, WindowFocus},
+    core::{math::UnitQuaternion, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    renderer::Camera,
+    shrev::{EventChannel, ReaderId},
+    winit::{DeviceEvent, Event},
+};
+use std::f32::consts::PI;
+
+use crate::{
+    settings::Settings,
+    states::game::{Look, Player, PlayerHidden},
+};
+
+// On ne peut pas regarder exactement a la verticale
+const MAX_PITCH: f32 = PI * 85.0 / 180.0;
+// Angles maximums autour du regard vers l'allee une fois cache
+const HIDDEN_MAX_YAW: f32 = PI / 3.0;
+const HIDDEN_MIN_PITCH: f32 = -PI / 9.0;
+const HIDDEN_MAX_PITCH: f32 = PI / 6.0;
+
+/// Tourne le joueur autour de l'axe vertical et la camera autour de son axe horizontal
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(MouseLookSystemDesc))]
+pub struct MouseLookSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<Event>,
+}
+
+impl MouseLookSystem {
+    pub fn new(event_reader: ReaderId<Event>) -> Self {
+        MouseLookSystem { event_reader }
+    }
+}
+
+impl<'s> System<'s> for MouseLookSystem {
+    type SystemData = (
+        Read<'s, EventChannel<Event>>,
+        Write<'s, Look>,
+        Read<'s, Settings>,
+        Read<'s, WindowFocus>,
+        Read<'s, HideCursor>,
+        Read<'s, PlayerHidden>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Camera>,
+    );
+
+    fn run(
+        &mut self,
+        (events, mut look, settings, focus, cursor, hidden, mut transforms, players, cameras): Self::SystemData,
+    ) {
+        // La souris n'est a nous que si elle est capturee (pas en pause)
+        let grabbed = focus.is_focused && cursor.hide;
+        let invert = if settings.invert_y { -1.0 } else { 1.0 };
+
+        for event in events.read(&mut self.event_reader) {
+            if let Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta: (x, y) },
+                ..
+            } = *event
+            {
+                if grabbed {
+                    look.yaw -= (x as f32 * settings.mouse_sensitivity).to_radians();
+                    look.pitch -= (y as f32 * settings.mouse_sensitivity * invert).to_radians();
+                }
+            }
+        }
+
+        look.yaw = wrap_angle(look.yaw);
+        look.pitch = look.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+        if hidden.is_busy() {
+            let t = hidden.progress * hidden.progress * (3.0 - 2.0 * hidden.progress);
+            restrict_view(&mut look, hidden.spot.2, t);
+        }
+
+        for (transform, _) in (&mut transforms, &players).join() {
+            transform.set_rotation(UnitQuaternion::from_euler_angles(0.0, look.yaw, 0.0));
+        }
+        for (transform, _) in (&mut transforms, &cameras).join() {
+            transform.set_rotation(UnitQuaternion::from_euler_angles(look.pitch, 0.0, 0.0));
+        }
+    }
+}
+
+fn wrap_angle(mut angle: f32) -> f32 {
+    while angle > PI {
+        angle -= 2.0 * PI;
+    }
+    while angle < -PI {
+        angle += 2.0 * PI;
+    }
+    angle
+}
+
+/// Limite le regard autour de l'allee, de plus en plus fort pendant la transition
+fn restrict_view(look: &mut Look, spot_yaw: f32, t: f32) {
+    let relative = wrap_angle(look.yaw - spot_yaw);
+    let max_yaw = PI + (HIDDEN_MAX_YAW - PI) * t;
+    look.yaw = wrap_angle(spot_yaw + relative.max(-max_yaw).min(max_yaw));
+
+    look.pitch = look
+        .pitch
+        .max(-MAX_PITCH + (HIDDEN_MIN_PITCH + MAX_PITCH) * t)
+        .min(MAX_PITCH + (HIDDEN_MAX_PITCH - MAX_PITCH) * t);
+}
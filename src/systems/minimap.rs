@@ -0,0 +1,82 @@
+use amethyst::{
+    core::{HiddenPropagate, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    input::{InputHandler, StringBindings},
+    ui::{UiImage, UiTransform},
+};
+
+use crate::{
+    states::game::{Player, UnlockedComputers},
+    ui::{to_map, HudScale, Minimap, MAP_LOCKED_COLOR, MAP_UNLOCKED_COLOR},
+};
+
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(MinimapSystemDesc))]
+pub struct MinimapSystem;
+
+impl<'s> System<'s> for MinimapSystem {
+    type SystemData = (
+        Write<'s, Minimap>,
+        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, UnlockedComputers>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiImage>,
+        WriteStorage<'s, HiddenPropagate>,
+        Read<'s, HudScale>,
+    );
+
+    fn run(
+        &mut self,
+        (mut minimap, input, uc, transforms, players, mut ui, mut images, mut hiddens, hud): Self::SystemData,
+    ) {
+        if let Some(pressed) = input.action_is_down("map") {
+            if pressed && !minimap.pressed {
+                minimap.pressed = true;
+                minimap.shown = !minimap.shown;
+
+                if let Some(root) = minimap.root {
+                    if minimap.shown {
+                        hiddens.remove(root);
+                    } else {
+                        hiddens
+                            .insert(root, HiddenPropagate::new())
+                            .expect("Failed to hide the minimap");
+                    }
+                }
+            }
+
+            if !pressed && minimap.pressed {
+                minimap.pressed = false;
+            }
+        }
+
+        if !minimap.shown {
+            return;
+        }
+
+        if let Some((transform, _)) = (&transforms, &players).join().next() {
+            let pos = transform.translation();
+            let (x, y) = to_map(&minimap.bounds, pos.x, pos.z);
+
+            if let Some(player) = minimap.player {
+                if let Some(marker) = ui.get_mut(player) {
+                    marker.local_x = x * hud.scale;
+                    marker.local_y = y * hud.scale;
+                }
+            }
+        }
+
+        for (computer_id, computer) in minimap.computers.iter().enumerate() {
+            if let Some(UiImage::SolidColor(color)) = images.get_mut(*computer) {
+                *color = if uc.unlocked_computers.binary_search(&computer_id).is_ok() {
+                    MAP_UNLOCKED_COLOR
+                } else {
+                    MAP_LOCKED_COLOR
+                };
+            }
+        }
+    }
+}
@@ -1,5 +1,24 @@
+pub mod audio;
 pub mod computer;
+pub mod effects;
+pub mod event_log;
+pub mod flashlight;
+pub mod graphics;
+pub mod graphics_menu;
 pub mod hide;
+pub mod hud;
+pub mod look;
+pub mod minimap;
 pub mod movement;
+pub mod objective;
+pub mod pages;
+pub mod panic;
+pub mod phase;
+pub mod posture;
+pub mod reload;
+pub mod save;
 pub mod screamer;
+pub mod stamina;
+pub mod stats;
+pub mod subtitles;
 pub mod use_system;
@@ -1,26 +1,45 @@
 use amethyst::{
-    controls::FlyControlTag,
+    assets::AssetStorage,
+    audio::{output::Output, Source},
     core::{
-        math::{convert, Unit, Vector3},
+        math::{convert, Unit, Vector2, Vector3},
         Time, Transform,
     },
     derive::SystemDesc,
     ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
     input::{get_input_axis_simple, InputHandler, StringBindings},
+    shrev::EventChannel,
 };
 
-use crate::ui::Reading;
 use crate::{
-    space::*,
-    states::game::{PlayerHidden, TimeToScreamer},
+    assets::GameAssets,
+    collision::{Walls, PLAYER_RADIUS},
+    level::Level,
+    play,
+    states::game::{GamePhase, Noise, Player, PlayerHidden, Posture, Stamina},
+    systems::phase::PhaseSystem,
 };
 
+const SPRINT_MULTIPLIER: f32 = 1.8;
+const CROUCH_MULTIPLIER: f32 = 0.5;
+// Stamina perdue par seconde de sprint
+const SPRINT_DRAIN: f32 = 0.25;
+
+// Distance parcourue entre deux pas
+const WALK_STRIDE: f32 = 0.8;
+const SPRINT_STRIDE: f32 = 1.2;
+const WALK_LOUDNESS: f32 = 0.2;
+const SPRINT_LOUDNESS: f32 = 1.0;
+const CROUCH_LOUDNESS: f32 = 0.4;
+
 #[derive(Debug, SystemDesc)]
 #[system_desc(name(RuptureMovementSystemDesc))]
 pub struct RuptureMovementSystem {
     speed: f32,
     right_input_axis: Option<String>,
     forward_input_axis: Option<String>,
+    #[system_desc(skip)]
+    distance_walked: f32,
 }
 
 impl RuptureMovementSystem {
@@ -33,52 +52,124 @@ impl RuptureMovementSystem {
             speed,
             right_input_axis,
             forward_input_axis,
+            distance_walked: 0.0,
         }
     }
 }
 
+impl PhaseSystem for RuptureMovementSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
 impl<'a> System<'a> for RuptureMovementSystem {
     type SystemData = (
         Read<'a, Time>,
         WriteStorage<'a, Transform>,
         Read<'a, InputHandler<StringBindings>>,
-        ReadStorage<'a, FlyControlTag>,
+        ReadStorage<'a, Player>,
         Write<'a, PlayerHidden>,
-        Read<'a, Reading>,
-        Read<'a, TimeToScreamer>,
+        Write<'a, Stamina>,
+        Read<'a, AssetStorage<Source>>,
+        Read<'a, GameAssets>,
+        Option<Read<'a, Output>>,
+        Write<'a, EventChannel<Noise>>,
+        Read<'a, Walls>,
+        Read<'a, Posture>,
+        Read<'a, Level>,
     );
 
     fn run(
         &mut self,
-        (time, mut transform, input, tag, mut hide, reading, since): Self::SystemData,
+        (
+            time,
+            mut transform,
+            input,
+            players,
+            mut hide,
+            mut stamina,
+            storage,
+            assets,
+            output,
+            mut noises,
+            walls,
+            posture,
+            level,
+        ): Self::SystemData,
     ) {
-        if reading.0 || hide.hidden || since.last_displayed != 0.0 {
+        stamina.sprinting = false;
+
+        if hide.is_busy() {
             return;
         }
 
         let x = get_input_axis_simple(&self.right_input_axis, &input);
         let z = get_input_axis_simple(&self.forward_input_axis, &input);
 
-        if let Some(dir) = Unit::try_new(Vector3::new(x, 0.0, z), convert(1.0e-6)) {
-            for (transform, _) in (&mut transform, &tag).join() {
-                let delta_sec = time.delta_seconds();
-                let old = transform.translation().clone();
+        // On avance dans la direction du regard, sans monter ni descendre
+        let dir = (&transform, &players)
+            .join()
+            .next()
+            .map(|(player, _)| player.rotation() * Vector3::new(x, 0.0, z))
+            .and_then(|dir| Unit::try_new(Vector3::new(dir.x, 0.0, dir.z), convert(1.0e-6)));
 
-                transform.append_translation_along(dir, delta_sec * self.speed);
+        if let Some(dir) = dir {
+            let delta_sec = time.delta_seconds();
 
-                let current = transform.translation().clone();
-                if !is_in_bound(current.x, old.z) {
-                    transform.set_translation_x(old.x);
-                }
-                if !is_in_bound(old.x, current.z) {
-                    transform.set_translation_z(old.z);
-                }
+            let mut speed = self.speed;
+            if posture.crouching {
+                speed *= CROUCH_MULTIPLIER;
+            } else if input.action_is_down("sprint").unwrap_or(false) && !stamina.exhausted {
+                stamina.sprinting = true;
+                stamina.value = (stamina.value - delta_sec * SPRINT_DRAIN).max(0.0);
+                stamina.exhausted = stamina.value <= 0.0;
+                speed *= SPRINT_MULTIPLIER;
+            }
 
-                let current = transform.translation().clone();
-                hide.can_hide = is_close_from_computer(current.x, current.z)
-                    || is_close_from_computer(current.x + 14.0, current.z);
+            let mut walked = None;
+            for (transform, _) in (&mut transform, &players).join() {
+                let old = *transform.translation();
 
-                transform.set_translation_y(old.y);
+                let current = walls.slide(
+                    Vector2::new(old.x, old.z),
+                    Vector2::new(dir.x, dir.z) * delta_sec * speed,
+                    PLAYER_RADIUS,
+                );
+                transform.set_translation_x(current.x);
+                transform.set_translation_z(current.y);
+
+                hide.can_hide = level.is_close_from_computer(current.x, current.y);
+
+                let distance = (current - Vector2::new(old.x, old.z)).norm();
+                walked = Some((current.x, current.y, distance));
+            }
+
+            if let Some((x, z, distance)) = walked {
+                self.distance_walked += distance;
+
+                let stride = if stamina.sprinting {
+                    SPRINT_STRIDE
+                } else {
+                    WALK_STRIDE
+                };
+                if self.distance_walked >= stride {
+                    self.distance_walked = 0.0;
+
+                    let loudness = if stamina.sprinting {
+                        SPRINT_LOUDNESS
+                    } else if posture.crouching {
+                        WALK_LOUDNESS * CROUCH_LOUDNESS
+                    } else {
+                        WALK_LOUDNESS
+                    };
+                    let step = assets.sound(if level.is_in_corridor(x, z) {
+                        "step_corridor"
+                    } else {
+                        "step_room"
+                    });
+
+                    play(&storage, &step, &output, 0.1 + 0.3 * loudness);
+                    noises.single_write(Noise { loudness });
+                }
             }
         }
     }
@@ -0,0 +1,74 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    shrev::EventChannel,
+    ui::UiText,
+};
+
+use crate::{
+    level::Level,
+    states::game::{Afit, GameEvent, GamePhase, Player},
+    systems::phase::PhaseSystem,
+    ui::{objective_tracker_text, Texts},
+};
+
+// Distance (en metres) au point de depart pour sortir du niveau
+const ESCAPE_RADIUS: f32 = 1.5;
+
+/// Affiche l'objectif du niveau, aussi pendant l'introduction
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(ObjectiveSystemDesc))]
+pub struct ObjectiveSystem;
+
+impl<'s> System<'s> for ObjectiveSystem {
+    type SystemData = (
+        Read<'s, Afit>,
+        Read<'s, Level>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Texts>,
+    );
+
+    fn run(&mut self, (afit, level, mut ui, texts): Self::SystemData) {
+        if let Some(text) = texts.objective.and_then(|objective| ui.get_mut(objective)) {
+            let objective = objective_tracker_text(&afit, &level);
+            if text.text != objective {
+                text.text = objective;
+            }
+        }
+    }
+}
+
+/// Termine le niveau quand le joueur s'enfuit, objectif rempli
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(EscapeSystemDesc))]
+pub struct EscapeSystem;
+
+impl PhaseSystem for EscapeSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
+impl<'s> System<'s> for EscapeSystem {
+    type SystemData = (
+        Read<'s, Afit>,
+        Read<'s, Level>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        Write<'s, EventChannel<GameEvent>>,
+    );
+
+    fn run(&mut self, (afit, level, transforms, players, mut events): Self::SystemData) {
+        if !afit.is_complete(&level.objective) {
+            return;
+        }
+
+        let (start_x, start_z) = level.start;
+        for (transform, _) in (&transforms, &players).join() {
+            let pos = transform.translation();
+            let (dx, dz) = (pos.x - start_x, pos.z - start_z);
+            if dx * dx + dz * dz < ESCAPE_RADIUS * ESCAPE_RADIUS {
+                events.single_write(GameEvent::GameWon);
+            }
+        }
+    }
+}
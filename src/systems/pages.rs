@@ -0,0 +1,123 @@
+use amethyst::{
+    core::{
+        math::{UnitQuaternion, Vector3},
+        Hidden, Time, Transform,
+    },
+    derive::SystemDesc,
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    shrev::EventChannel,
+    ui::UiText,
+};
+
+use crate::{
+    level::Level,
+    states::game::{Afit, AfitPage, GameEvent, GamePhase, Player, PlayerHidden, PAGE_HEIGHT},
+    systems::phase::PhaseSystem,
+    ui::{code_text, pages_text, Texts},
+};
+
+// Distance (en metres) a laquelle on ramasse une page en marchant dessus
+const PICKUP_RADIUS: f32 = 0.6;
+const BOB_HEIGHT: f32 = 0.05;
+const BOB_SPEED: f32 = 2.0;
+
+/// Ramasse les pages d'AFIT et les garde tournees vers le joueur
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(PageSystemDesc))]
+pub struct PageSystem {
+    /// Texte du HUD et nombre de pages qu'il affiche
+    pages_found: Option<(Entity, usize)>,
+}
+
+// Pas de page ramassee pendant l'introduction, avant le debut de la partie
+impl PhaseSystem for PageSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
+impl<'s> System<'s> for PageSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, Time>,
+        Write<'s, Afit>,
+        Read<'s, PlayerHidden>,
+        ReadStorage<'s, AfitPage>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Texts>,
+        Read<'s, Level>,
+        Write<'s, EventChannel<GameEvent>>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            time,
+            mut afit,
+            hidden,
+            pages,
+            players,
+            mut transforms,
+            mut hiddens,
+            mut ui,
+            texts,
+            level,
+            mut events,
+        ): Self::SystemData,
+    ) {
+        let player = match (&transforms, &players).join().next() {
+            Some((transform, _)) => *transform.translation(),
+            None => return,
+        };
+
+        let now = time.absolute_time_seconds();
+        for (page, transform) in (&pages, &mut transforms).join() {
+            if afit.pages.contains(&page.id) {
+                continue;
+            }
+
+            let pos = transform.translation();
+            let (dx, dz) = (player.x - pos.x, player.z - pos.z);
+            if !hidden.is_busy() && dx * dx + dz * dz < PICKUP_RADIUS * PICKUP_RADIUS {
+                afit.pages.push(page.id);
+                // Chaque page contient aussi un morceau de code
+                afit.code_found += 1;
+                events.single_write(GameEvent::PageCollected);
+                continue;
+            }
+
+            // Le sprite est dans le plan xy, on tourne sa face (+z) vers le joueur
+            transform.set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                dx.atan2(dz),
+            ));
+            let phase = now as f32 * BOB_SPEED + page.id as f32;
+            transform.set_translation_y(PAGE_HEIGHT + phase.sin() * BOB_HEIGHT);
+        }
+
+        // Aussi apres un chargement rapide, qui peut faire reapparaitre des pages,
+        // et au debut de chaque niveau
+        let shown = texts.pages.map(|pages| (pages, afit.pages.len()));
+        if self.pages_found == shown {
+            return;
+        }
+        self.pages_found = shown;
+
+        for (entity, page) in (&entities, &pages).join() {
+            if afit.pages.contains(&page.id) {
+                let _ = hiddens.insert(entity, Hidden);
+            } else {
+                hiddens.remove(entity);
+            }
+        }
+
+        if let Some(text) = texts.pages.and_then(|pages| ui.get_mut(pages)) {
+            text.text = pages_text(afit.pages.len(), level.pages.len());
+        }
+        if let Some(text) = texts.code.and_then(|code| ui.get_mut(code)) {
+            text.text = code_text(afit.code_found, level.objective.codes);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use amethyst::{
+    assets::AssetStorage,
+    audio::{output::Output, Source},
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use crate::{assets::GameAssets, play, states::game::Panic, ui::Texts};
+
+// Pas de battements de coeur en dessous
+const HEARTBEAT_THRESHOLD: f32 = 0.2;
+// Opacite de la vignette quand la panique est au maximum
+const VIGNETTE_MAX_ALPHA: f32 = 0.9;
+
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(PanicSystemDesc))]
+pub struct PanicSystem;
+
+impl<'s> System<'s> for PanicSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Write<'s, Panic>,
+        Read<'s, AssetStorage<Source>>,
+        Read<'s, GameAssets>,
+        Option<Read<'s, Output>>,
+        Read<'s, Texts>,
+        WriteStorage<'s, Tint>,
+    );
+
+    fn run(
+        &mut self,
+        (time, mut panic, storage, assets, output, texts, mut tints): Self::SystemData,
+    ) {
+        // Le coeur bat de plus en plus vite
+        let interval = 1.2 - 0.8 * panic.value as f64;
+        if panic.value > HEARTBEAT_THRESHOLD
+            && time.absolute_time_seconds() - panic.last_heartbeat > interval
+        {
+            panic.last_heartbeat = time.absolute_time_seconds();
+            play(
+                &storage,
+                &assets.sound("heartbeat"),
+                &output,
+                0.2 + 0.6 * panic.value,
+            );
+        }
+
+        if let Some(vignette) = texts.vignette {
+            if let Some(tint) = tints.get_mut(vignette) {
+                tint.0 = Srgba::new(1., 1., 1., panic.value * VIGNETTE_MAX_ALPHA);
+            }
+        }
+    }
+}
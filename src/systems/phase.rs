@@ -0,0 +1,50 @@
+use amethyst::{
+    core::SystemDesc,
+    ecs::{Read, System, SystemData, World},
+};
+
+use crate::states::game::GamePhase;
+
+/// Systeme de jeu qui ne tourne que dans certaines phases de la partie
+pub trait PhaseSystem {
+    const PHASES: &'static [GamePhase];
+}
+
+/// Fait tourner `S` uniquement dans les phases qu'il declare
+#[derive(Debug)]
+pub struct InPhases<S>(pub S);
+
+impl<'s, S> System<'s> for InPhases<S>
+where
+    S: System<'s> + PhaseSystem,
+    S::SystemData: SystemData<'s>,
+{
+    type SystemData = (Read<'s, GamePhase>, S::SystemData);
+
+    fn run(&mut self, (phase, data): Self::SystemData) {
+        if S::PHASES.contains(&*phase) {
+            self.0.run(data);
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        <Read<'_, GamePhase> as SystemData>::setup(world);
+        self.0.setup(world);
+    }
+}
+
+/// `InPhases` pour les systemes construits par un `SystemDesc`
+#[derive(Debug)]
+pub struct InPhasesDesc<D>(pub D);
+
+impl<'a, 'b, S, D> SystemDesc<'a, 'b, InPhases<S>> for InPhasesDesc<D>
+where
+    S: for<'c> System<'c> + PhaseSystem + Send + 'a,
+    InPhases<S>: for<'c> System<'c>,
+    D: SystemDesc<'a, 'b, S>,
+{
+    fn build(self, world: &mut World) -> InPhases<S> {
+        <Read<'_, GamePhase> as SystemData>::setup(world);
+        InPhases(self.0.build(world))
+    }
+}
@@ -0,0 +1,102 @@
+use amethyst::{
+    core::{
+        math::{Vector2, Vector3},
+        Time, Transform,
+    },
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    input::{InputHandler, StringBindings},
+    renderer::Camera,
+};
+
+use crate::{
+    collision::{Walls, PLAYER_RADIUS},
+    states::game::{GamePhase, Player, PlayerHidden, Posture, CAMERA_HEIGHT},
+    systems::phase::PhaseSystem,
+};
+
+// Hauteur perdue par la camera quand on est accroupi
+const CROUCH_DROP: f32 = 0.55;
+const LEAN_DISTANCE: f32 = 0.4;
+// Vitesse de l'interpolation, plus c'est grand plus c'est rapide
+const POSTURE_SPEED: f32 = 8.0;
+
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(PostureSystemDesc))]
+pub struct PostureSystem;
+
+impl PhaseSystem for PostureSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
+impl<'s> System<'s> for PostureSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, InputHandler<StringBindings>>,
+        Write<'s, Posture>,
+        Read<'s, PlayerHidden>,
+        Read<'s, Walls>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Player>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            time,
+            input,
+            mut posture,
+            hidden,
+            walls,
+            mut transforms,
+            cameras,
+            players,
+        ): Self::SystemData,
+    ) {
+        let free = !hidden.is_busy();
+        let action = |name| free && input.action_is_down(name).unwrap_or(false);
+
+        posture.crouching = action("crouch");
+        let crouch_target = if posture.crouching { 1.0 } else { 0.0 };
+        let lean_target = match (action("lean_left"), action("lean_right")) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => 0.0,
+        };
+
+        let blend = 1.0 - (-time.delta_seconds() * POSTURE_SPEED).exp();
+        posture.crouch += (crouch_target - posture.crouch) * blend;
+        posture.lean += (lean_target - posture.lean) * blend;
+
+        // La camera est deplacee par le HidingSystem
+        if hidden.is_busy() {
+            return;
+        }
+
+        let (base, rotation) = match (&transforms, &players).join().next() {
+            Some((transform, _)) => {
+                let pos = transform.translation();
+                (Vector2::new(pos.x, pos.z), *transform.rotation())
+            }
+            None => return,
+        };
+
+        let right = rotation * Vector3::x();
+        let right = Vector2::new(right.x, right.z)
+            .try_normalize(1.0e-6)
+            .unwrap_or_else(Vector2::zeros);
+
+        // On ne se penche pas a travers les murs
+        let leaned = walls.slide(base, right * posture.lean * LEAN_DISTANCE, PLAYER_RADIUS);
+        let offset = rotation.inverse() * Vector3::new(leaned.x - base.x, 0.0, leaned.y - base.y);
+
+        for (transform, _) in (&mut transforms, &cameras).join() {
+            transform.set_translation_xyz(
+                offset.x,
+                CAMERA_HEIGHT - posture.crouch * CROUCH_DROP,
+                offset.z,
+            );
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use amethyst::{
+    assets::{AssetStorage, Handle},
+    core::{ParentHierarchy, Transform},
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadExpect, System, SystemData, Write, WriteStorage},
+    ui::{FontAsset, FontHandle, UiText},
+};
+use amethyst_gltf::GltfSceneAsset;
+use log::info;
+
+use crate::{
+    assets::GameAssets,
+    level::Level,
+    states::game::{scene_transform, SceneRoot},
+};
+
+/// Applique les assets recharges a chaud (uniquement avec `--dev`)
+///
+/// Les sons sont relus depuis leur handle a chaque lecture, il n'y a rien a faire pour eux.
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(AssetReloadSystemDesc))]
+pub struct AssetReloadSystem {
+    scene_version: Option<u32>,
+    font_version: Option<u32>,
+    /// Handle actuellement utilise par les textes du HUD
+    font: Option<FontHandle>,
+}
+
+impl<'s> System<'s> for AssetReloadSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, GameAssets>,
+        Read<'s, AssetStorage<GltfSceneAsset>>,
+        Write<'s, AssetStorage<FontAsset>>,
+        Write<'s, SceneRoot>,
+        ReadExpect<'s, ParentHierarchy>,
+        WriteStorage<'s, Handle<GltfSceneAsset>>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Level>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            assets,
+            scenes,
+            mut fonts,
+            mut root,
+            hierarchy,
+            mut scene_handles,
+            mut transforms,
+            mut texts,
+            level,
+        ): Self::SystemData,
+    ) {
+        if let Some(scene) = assets.scene(&level.scene) {
+            let version = scenes.get_version(&scene);
+            if reloaded(self.scene_version, version) {
+                info!("Reloading scene {}", level.scene);
+
+                // Le prefab n'est instancie qu'une fois, on recree donc toute la scene
+                if let Some(old) = root.0.take() {
+                    for child in (&hierarchy.all_children(old)).join() {
+                        let _ = entities.delete(entities.entity(child));
+                    }
+                    let _ = entities.delete(old);
+                }
+
+                let entity = entities
+                    .build_entity()
+                    .with(scene, &mut scene_handles)
+                    .with(scene_transform(), &mut transforms)
+                    .build();
+                root.0 = Some(entity);
+            }
+            self.scene_version = version;
+        }
+
+        if let Some(font) = assets.font("hud") {
+            let version = fonts.get_version(&font);
+            if reloaded(self.font_version, version) {
+                info!("Reloading font hud");
+
+                // Le cache de glyphes d'amethyst_ui est indexe par handle : on donne aux
+                // textes un nouveau handle pour qu'ils utilisent la police rechargee
+                let current = self.font.take().unwrap_or_else(|| font.clone());
+                if let Some(fresh) = fonts.clone_asset(&font) {
+                    for text in (&mut texts).join() {
+                        if text.font.id() == current.id() {
+                            text.font = fresh.clone();
+                        }
+                    }
+                    self.font = Some(fresh);
+                } else {
+                    self.font = Some(current);
+                }
+            }
+            self.font_version = version;
+        }
+    }
+}
+
+/// Vrai si l'asset a change de version depuis la derniere frame
+fn reloaded(previous: Option<u32>, current: Option<u32>) -> bool {
+    matches!((previous, current), (Some(previous), Some(current)) if previous != current)
+}
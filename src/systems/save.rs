@@ -0,0 +1,107 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    input::{InputHandler, StringBindings},
+    ui::UiText,
+};
+
+use crate::{
+    level::Level,
+    save::{load_game, write_game, SaveGame},
+    states::game::{Afit, GamePhase, Player, PlayerHidden, TimeToScreamer, UnlockedComputers},
+    systems::phase::PhaseSystem,
+    ui::{code_text, Texts},
+};
+
+#[derive(Debug, Default, SystemDesc)]
+#[system_desc(name(SaveSystemDesc))]
+pub struct SaveSystem {
+    code_found: Option<u8>,
+    save_pressed: bool,
+    load_pressed: bool,
+}
+
+// Pas de sauvegarde d'une partie perdue, gagnee ou en pause
+impl PhaseSystem for SaveSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Intro, GamePhase::Playing];
+}
+
+impl<'s> System<'s> for SaveSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, InputHandler<StringBindings>>,
+        Write<'s, Afit>,
+        Write<'s, UnlockedComputers>,
+        Write<'s, TimeToScreamer>,
+        Write<'s, PlayerHidden>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, UiText>,
+        Read<'s, Texts>,
+        Read<'s, Level>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            time,
+            input,
+            mut afit,
+            mut uc,
+            mut since,
+            mut hidden,
+            mut transforms,
+            players,
+            mut ui,
+            texts,
+            level,
+        ): Self::SystemData,
+    ) {
+        // Ni sous un bureau, ou l'on ne pourrait pas reprendre
+        if hidden.is_busy() {
+            return;
+        }
+
+        let save = input.action_is_down("quicksave").unwrap_or(false);
+        let autosave = matches!(self.code_found, Some(found) if afit.code_found > found);
+        self.code_found = Some(afit.code_found);
+
+        if (save && !self.save_pressed) || autosave {
+            if let Some((transform, _)) = (&transforms, &players).join().next() {
+                write_game(&SaveGame::new(
+                    &level,
+                    &afit,
+                    &uc,
+                    &since,
+                    &hidden,
+                    transform,
+                    time.absolute_time_seconds(),
+                ));
+            }
+        }
+        self.save_pressed = save;
+
+        let load = input.action_is_down("quickload").unwrap_or(false);
+        if load && !self.load_pressed {
+            // Une sauvegarde d'un autre niveau se charge depuis le choix du niveau
+            if let Some(mut save) = load_game().filter(|save| save.level == level.index) {
+                save.rebase(time.absolute_time_seconds());
+                save.place_player(&mut transforms, &players);
+
+                *afit = save.afit;
+                *uc = save.unlocked_computers;
+                *since = save.time_to_screamer;
+                *hidden = save.player_hidden;
+                self.code_found = Some(afit.code_found);
+
+                if let Some(code) = texts.code {
+                    if let Some(text) = ui.get_mut(code) {
+                        text.text = code_text(afit.code_found, level.objective.codes);
+                    }
+                }
+            }
+        }
+        self.load_pressed = load;
+    }
+}
@@ -1,77 +1,114 @@
 use amethyst::{
-    assets::AssetStorage,
-    audio::{output::Output, Source},
-    core::Time,
+    core::{HiddenPropagate, Time},
     derive::SystemDesc,
     ecs::{Read, System, SystemData, Write, WriteStorage},
-    ui::UiTransform,
+    shrev::{EventChannel, ReaderId},
 };
 
 use crate::{
-    play,
-    states::game::{Afit, PlayerHidden, Screamer, Sounds, TimeToScreamer, MAX_CODE},
-    ui::Reading,
+    level::Level,
+    states::game::{
+        Afit, Flashlight, GameEvent, GamePhase, Noise, Panic, PlayerHidden, Screamer,
+        TimeToScreamer, PANIC_BREATHING,
+    },
+    systems::phase::PhaseSystem,
 };
 
+// Secondes d'avance prises par Bashar pour un bruit de volume 1
+const NOISE_REACTION: f32 = 0.15;
+// Secondes d'avance prises par Bashar par seconde de lampe allumee
+const LIGHT_ATTRACTION: f64 = 0.1;
+
 #[derive(Debug, SystemDesc)]
 #[system_desc(name(ScreamerSystemDesc))]
-pub struct ScreamerSystem;
+pub struct ScreamerSystem {
+    #[system_desc(event_channel_reader)]
+    noise_reader: ReaderId<Noise>,
+}
+
+impl ScreamerSystem {
+    pub fn new(noise_reader: ReaderId<Noise>) -> Self {
+        ScreamerSystem { noise_reader }
+    }
+}
+
+impl PhaseSystem for ScreamerSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
 
 impl<'s> System<'s> for ScreamerSystem {
     type SystemData = (
         Read<'s, Time>,
-        Read<'s, AssetStorage<Source>>,
-        Read<'s, Sounds>,
         Read<'s, Screamer>,
-        WriteStorage<'s, UiTransform>,
-        Option<Read<'s, Output>>,
+        WriteStorage<'s, HiddenPropagate>,
         Read<'s, Afit>,
         Write<'s, TimeToScreamer>,
         Read<'s, PlayerHidden>,
-        Read<'s, Reading>,
+        Read<'s, EventChannel<Noise>>,
+        Read<'s, Panic>,
+        Read<'s, Flashlight>,
+        Read<'s, Level>,
+        Write<'s, EventChannel<GameEvent>>,
     );
 
     fn run(
         &mut self,
-        (time, storage, sound, screamer, mut ui, output, afit, mut since, hidden, reading): Self::SystemData,
+        (
+            time,
+            screamer,
+            mut hiddens,
+            afit,
+            mut since,
+            hidden,
+            noises,
+            panic,
+            flashlight,
+            level,
+            mut events,
+        ): Self::SystemData,
     ) {
-        if reading.0 {
-            return;
+        if since.at == 0.0 {
+            since.at = time.absolute_time_seconds()
+                + level.difficulty.first_screamer
+                + rand::random::<f64>() * 10.0;
         }
 
-        if since.at == 0.0 {
-            since.at = time.absolute_time_seconds() + 15.0 + rand::random::<f64>() * 10.0;
+        // Bashar entend les pas
+        for noise in noises.read(&mut self.noise_reader) {
+            if !since.played {
+                since.at -= (noise.loudness * NOISE_REACTION) as f64;
+            }
+        }
+
+        // Et la lumiere attire son regard
+        if flashlight.on && !since.played {
+            since.at -= time.delta_seconds() as f64 * LIGHT_ATTRACTION;
         }
 
         if time.absolute_time_seconds() > since.at - (1.0 + (3.0 / (afit.code_found as f64 + 1.0)))
             && !since.played
         {
-            play(&storage, &sound.coming, &output, 0.65);
+            events.single_write(GameEvent::BasharComing);
             since.played = true;
         }
 
-        if time.absolute_time_seconds() - since.last_displayed > 3.5 && since.display {
-            std::process::exit(0);
-        }
-
         if time.absolute_time_seconds() > since.at {
-            if !hidden.hidden {
+            // Bashar entend aussi le joueur paniquer sous son bureau, ou voit sa lampe
+            if !hidden.is_safe() || panic.value >= PANIC_BREATHING || flashlight.on {
                 if let Some(bashar) = screamer.bashar {
-                    if let Some(transform) = ui.get_mut(bashar) {
-                        transform.width = 1024.;
-                        transform.height = 768.;
-                        since.last_displayed = time.absolute_time_seconds();
-                        since.display = true;
-                        play(&storage, &sound.screamer, &output, 0.9);
+                    if hiddens.remove(bashar).is_some() {
+                        events.single_write(GameEvent::PlayerCaught);
                     }
                 }
+            } else {
+                events.single_write(GameEvent::BasharLeft);
             }
 
             since.played = false;
+            let codes = level.objective.codes as f64;
             since.at = time.absolute_time_seconds()
-                + 5.0
-                + (MAX_CODE as f64 / (afit.code_found as f64 + 1.0))
-                + rand::random::<f64>() * 10.0;
+                + (5.0 + (codes / (afit.code_found as f64 + 1.0)) + rand::random::<f64>() * 10.0)
+                    * level.difficulty.screamer_delay;
         }
     }
 }
@@ -0,0 +1,56 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write, WriteStorage},
+    ui::{UiImage, UiTransform},
+};
+
+use crate::{
+    states::game::{GamePhase, Stamina},
+    systems::phase::PhaseSystem,
+    ui::{HudScale, Texts, STAMINA_BAR_WIDTH},
+};
+
+// Stamina regagnee par seconde sans sprinter
+const STAMINA_RECOVERY: f32 = 0.15;
+// Stamina a retrouver apres s'etre epuise pour sprinter a nouveau
+const STAMINA_RECOVERED: f32 = 0.25;
+
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(StaminaSystemDesc))]
+pub struct StaminaSystem;
+
+impl PhaseSystem for StaminaSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
+impl<'s> System<'s> for StaminaSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Write<'s, Stamina>,
+        Read<'s, Texts>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiImage>,
+        Read<'s, HudScale>,
+    );
+
+    fn run(&mut self, (time, mut stamina, texts, mut ui, mut images, hud): Self::SystemData) {
+        if !stamina.sprinting {
+            stamina.value = (stamina.value + time.delta_seconds() * STAMINA_RECOVERY).min(1.0);
+        }
+        if stamina.value >= STAMINA_RECOVERED {
+            stamina.exhausted = false;
+        }
+
+        if let Some(bar) = texts.stamina {
+            if let Some(transform) = ui.get_mut(bar) {
+                transform.width = STAMINA_BAR_WIDTH * stamina.value * hud.scale;
+            }
+
+            // Barre cachee quand le joueur est repose
+            if let Some(UiImage::SolidColor(color)) = images.get_mut(bar) {
+                color[3] = if stamina.value < 1.0 { 0.35 } else { 0.0 };
+            }
+        }
+    }
+}
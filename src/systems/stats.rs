@@ -0,0 +1,68 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    level::Level,
+    states::game::{Afit, GameEvent},
+    stats::{write_stats, CurrentRun, Stats},
+};
+
+/// Tient les statistiques du joueur a jour au fil des evenements
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(StatsSystemDesc))]
+pub struct StatsSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<GameEvent>,
+}
+
+impl StatsSystem {
+    pub fn new(event_reader: ReaderId<GameEvent>) -> Self {
+        StatsSystem { event_reader }
+    }
+}
+
+impl<'s> System<'s> for StatsSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, Afit>,
+        Read<'s, Level>,
+        Write<'s, Stats>,
+        Write<'s, CurrentRun>,
+        Read<'s, EventChannel<GameEvent>>,
+    );
+
+    fn run(&mut self, (time, afit, level, mut stats, mut run, events): Self::SystemData) {
+        let now = time.absolute_time_seconds();
+
+        for event in events.read(&mut self.event_reader) {
+            match event {
+                GameEvent::PlayerHid => stats.times_hidden += 1,
+                GameEvent::CodeCollected | GameEvent::PageCollected => {
+                    stats.most_code_found = stats.most_code_found.max(afit.code_found);
+
+                    if afit.is_complete(&level.objective) && !run.completed {
+                        run.completed = true;
+
+                        let completion_time = now - run.started_at;
+                        stats.best_completion_time = Some(
+                            stats
+                                .best_completion_time
+                                .map_or(completion_time, |best| best.min(completion_time)),
+                        );
+                        write_stats(&stats);
+                    }
+                }
+                GameEvent::PlayerCaught => {
+                    stats.total_deaths += 1;
+                    stats.total_survival_time += now - run.started_at;
+                    write_stats(&stats);
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use amethyst::{
+    core::{math::Vector3, Time, Transform},
+    derive::SystemDesc,
+    ecs::{Entity, Join, Read, ReadStorage, System, SystemData, WriteStorage},
+    shrev::{EventChannel, ReaderId},
+    ui::{UiImage, UiText},
+};
+use std::f32::consts::FRAC_PI_4;
+
+use crate::{
+    level::Level,
+    settings::Settings,
+    states::game::{GameEvent, Player},
+    ui::{caption_text, Texts, SUBTITLE_LINES},
+};
+
+// Duree (en secondes) d'affichage d'un sous-titre
+const CAPTION_DURATION: f64 = 4.0;
+// Duree et opacite du flash quand un ordinateur demarre
+const FLASH_DURATION: f64 = 0.8;
+const FLASH_ALPHA: f32 = 0.5;
+
+/// Direction d'un son par rapport au regard du joueur
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Front,
+    Left,
+    Right,
+    Behind,
+}
+
+#[derive(Debug)]
+struct Caption {
+    text: &'static str,
+    /// Position (x, z) du son, sans direction s'il est absent
+    source: Option<(f32, f32)>,
+    shown_at: f64,
+}
+
+/// Sous-titre les sons du jeu, avec la direction d'ou ils viennent
+#[derive(Debug, SystemDesc)]
+#[system_desc(name(SubtitleSystemDesc))]
+pub struct SubtitleSystem {
+    #[system_desc(event_channel_reader)]
+    event_reader: ReaderId<GameEvent>,
+    /// Le plus recent en dernier
+    #[system_desc(skip)]
+    captions: Vec<Caption>,
+    /// Cotes a faire clignoter (gauche, droite) et debut du flash
+    #[system_desc(skip)]
+    flash: Option<((bool, bool), f64)>,
+    /// Texte du HUD ou s'affichent les sous-titres, recree a chaque niveau
+    #[system_desc(skip)]
+    subtitles: Option<Entity>,
+}
+
+impl SubtitleSystem {
+    pub fn new(event_reader: ReaderId<GameEvent>) -> Self {
+        SubtitleSystem {
+            event_reader,
+            captions: Vec::new(),
+            flash: None,
+            subtitles: None,
+        }
+    }
+}
+
+impl<'s> System<'s> for SubtitleSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, Settings>,
+        Read<'s, EventChannel<GameEvent>>,
+        Read<'s, Level>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, UiImage>,
+        Read<'s, Texts>,
+    );
+
+    fn run(
+        &mut self,
+        (time, settings, events, level, transforms, players, mut ui, mut images, texts): Self::SystemData,
+    ) {
+        let now = time.absolute_time_seconds();
+        let player = (&transforms, &players).join().next().map(|(t, _)| t);
+
+        // Rien du niveau precedent sur le HUD du suivant
+        if self.subtitles != texts.subtitles {
+            self.subtitles = texts.subtitles;
+            self.captions.clear();
+            self.flash = None;
+        }
+
+        for event in events.read(&mut self.event_reader) {
+            let text = match caption_text(event) {
+                Some(text) => text,
+                None => continue,
+            };
+
+            // Seuls les ordinateurs ont une position, les pas et le cri de Bashar n'en ont pas
+            let source = match event {
+                GameEvent::ComputerUnlocked(computer_id) => {
+                    Some(level.computer_position(*computer_id))
+                }
+                _ => None,
+            };
+            self.captions.push(Caption {
+                text,
+                source,
+                shown_at: now,
+            });
+
+            if let GameEvent::ComputerUnlocked(_) = event {
+                let sides = match source.zip(player).map(|(s, p)| direction(p, s)) {
+                    Some(Direction::Left) => (true, false),
+                    Some(Direction::Right) => (false, true),
+                    _ => (true, true),
+                };
+                self.flash = Some((sides, now));
+            }
+        }
+
+        self.captions
+            .retain(|caption| now - caption.shown_at < CAPTION_DURATION);
+        let skip = self.captions.len().saturating_sub(SUBTITLE_LINES);
+
+        if let Some(text) = texts.subtitles.and_then(|subtitles| ui.get_mut(subtitles)) {
+            let lines = if settings.subtitles {
+                self.captions
+                    .iter()
+                    .skip(skip)
+                    .map(|caption| {
+                        let text = caption.text;
+                        match caption.source.zip(player).map(|(s, p)| direction(p, s)) {
+                            Some(Direction::Front) => format!("^ {} ^", text),
+                            Some(Direction::Left) => format!("< {}", text),
+                            Some(Direction::Right) => format!("{} >", text),
+                            Some(Direction::Behind) => format!("v {} v", text),
+                            None => text.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else {
+                String::new()
+            };
+
+            if text.text != lines {
+                text.text = lines;
+            }
+        }
+
+        let (left, right, alpha) = match self.flash {
+            Some(((left, right), at)) if now - at < FLASH_DURATION && settings.subtitles => {
+                let alpha = FLASH_ALPHA * (1.0 - ((now - at) / FLASH_DURATION) as f32);
+                (left, right, alpha)
+            }
+            _ => {
+                self.flash = None;
+                (false, false, 0.0)
+            }
+        };
+        for (bar, on) in [(texts.flash_left, left), (texts.flash_right, right)].iter() {
+            if let Some(UiImage::SolidColor(color)) = bar.and_then(|bar| images.get_mut(bar)) {
+                color[3] = if *on { alpha } else { 0.0 };
+            }
+        }
+    }
+}
+
+/// D'ou vient un son en (x, z), vu par le joueur
+fn direction(player: &Transform, (x, z): (f32, f32)) -> Direction {
+    let pos = player.translation();
+    let to = Vector3::new(x - pos.x, 0.0, z - pos.z);
+    let forward = player.rotation() * -Vector3::z();
+    let right = player.rotation() * Vector3::x();
+
+    let angle = to.dot(&right).atan2(to.dot(&forward));
+    if angle.abs() < FRAC_PI_4 {
+        Direction::Front
+    } else if angle.abs() > 3.0 * FRAC_PI_4 {
+        Direction::Behind
+    } else if angle < 0.0 {
+        Direction::Left
+    } else {
+        Direction::Right
+    }
+}
@@ -1,22 +1,27 @@
 use amethyst::{
-    controls::FlyControlTag,
     core::Transform,
     derive::SystemDesc,
     ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
     input::{InputHandler, StringBindings},
+    shrev::EventChannel,
     ui::UiText,
 };
 
 use crate::{
-    space::*,
-    states::game::{Afit, PlayerHidden, UnlockedComputers, MAX_CODE},
-    ui::Texts,
+    level::Level,
+    states::game::{Afit, GameEvent, GamePhase, Player, PlayerHidden, UnlockedComputers},
+    systems::phase::PhaseSystem,
+    ui::{code_text, Texts},
 };
 
 #[derive(SystemDesc)]
 #[system_desc(name(UseSystemDesc))]
 pub struct UseSystem;
 
+impl PhaseSystem for UseSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Playing];
+}
+
 impl<'s> System<'s> for UseSystem {
     type SystemData = (
         ReadStorage<'s, Transform>,
@@ -26,18 +31,31 @@ impl<'s> System<'s> for UseSystem {
         Write<'s, Afit>,
         Write<'s, UnlockedComputers>,
         Read<'s, PlayerHidden>,
-        ReadStorage<'s, FlyControlTag>,
+        ReadStorage<'s, Player>,
+        Read<'s, Level>,
+        Write<'s, EventChannel<GameEvent>>,
     );
 
     fn run(
         &mut self,
-        (transforms, mut ui, texts, input, mut afit, mut uc, hidden, tags): Self::SystemData,
+        (
+            transforms,
+            mut ui,
+            texts,
+            input,
+            mut afit,
+            mut uc,
+            hidden,
+            players,
+            level,
+            mut events,
+        ): Self::SystemData,
     ) {
-        for (transform, _) in (&transforms, &tags).join() {
+        for (transform, _) in (&transforms, &players).join() {
             let pos = transform.translation();
             if let Some(_use) = texts._use {
                 if let Some(text) = ui.get_mut(_use) {
-                    if is_close_from_computer(pos.x, pos.z) {
+                    if level.is_close_from_computer(pos.x, pos.z) {
                         text.text = "Appuyez sur 'J' pour recuperer le code".to_string();
                     } else {
                         text.text = String::new();
@@ -45,22 +63,19 @@ impl<'s> System<'s> for UseSystem {
                 }
             }
 
-            if is_close_from_computer(pos.x, pos.z) {
+            if level.is_close_from_computer(pos.x, pos.z) {
                 if let Some(pressed) = input.action_is_down("use") {
-                    if !hidden.hidden && pressed {
+                    if !hidden.is_busy() && pressed {
                         for i in 0..uc.unlocked_computers.len() {
-                            if is_able_to_use_computer(&transform, uc.unlocked_computers[i]) {
+                            if level.is_able_to_use_computer(&transform, uc.unlocked_computers[i]) {
                                 uc.unlocked_computers.remove(i);
                                 afit.code_found += 1;
+                                events.single_write(GameEvent::CodeCollected);
 
                                 if let Some(code) = texts.code {
                                     if let Some(text) = ui.get_mut(code) {
-                                        text.text = format!(
-                                            "Tests passes a {}%",
-                                            (afit.code_found.min(MAX_CODE) as f32 / MAX_CODE as f32
-                                                * 100.0)
-                                                as i32
-                                        );
+                                        text.text =
+                                            code_text(afit.code_found, level.objective.codes);
                                     }
                                 }
                                 break;
@@ -1,10 +1,30 @@
 use amethyst::{
+    assets::{AssetStorage, Loader},
+    config::Config,
+    core::{HiddenPropagate, Parent},
     derive::SystemDesc,
-    ecs::{Entity, Read, System, SystemData, World, Write, WriteStorage},
+    ecs::{
+        Component, DenseVecStorage, Entity, Read, System, SystemData, World, Write, WriteStorage,
+    },
     input::{InputHandler, StringBindings},
     prelude::{Builder, WorldExt},
-    ui::{Anchor, FontHandle, LineMode, UiText, UiTransform},
-    winit::MouseButton,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender},
+    ui::{
+        get_default_font, Anchor, FontAsset, FontHandle, LineMode, Stretch, UiImage, UiText,
+        UiTransform,
+    },
+    window::DisplayConfig,
+};
+use log::warn;
+
+use crate::{
+    level::{Level, Levels, Objective},
+    save::data_path,
+    settings::Settings,
+    space::*,
+    states::game::{Afit, GameEvent, GamePhase},
+    stats::Stats,
+    systems::phase::PhaseSystem,
 };
 
 #[derive(Default)]
@@ -12,13 +32,260 @@ pub struct Texts {
     pub hide: Option<Entity>,
     pub _use: Option<Entity>,
     pub code: Option<Entity>,
+    pub pages: Option<Entity>,
+    pub objective: Option<Entity>,
+    /// Lignes du journal, de la plus recente a la plus ancienne
+    pub log: Vec<Entity>,
     pub menu: Option<Entity>,
+    pub stamina: Option<Entity>,
+    pub vignette: Option<Entity>,
+    /// Sous-titres des sons, en bas au centre
+    pub subtitles: Option<Entity>,
+    /// Bords de l'ecran qui s'allument quand un ordinateur demarre
+    pub flash_left: Option<Entity>,
+    pub flash_right: Option<Entity>,
+}
+
+/// Echelle du HUD par rapport aux dimensions de `display.ron`
+pub struct HudScale {
+    pub reference: (f32, f32),
+    pub scale: f32,
+}
+
+impl Default for HudScale {
+    fn default() -> Self {
+        HudScale {
+            reference: (1024., 768.),
+            scale: 1.,
+        }
+    }
+}
+
+/// Element du HUD redimensionne avec la fenetre, ses valeurs de reference sont lues a la creation
+#[derive(Default)]
+pub struct HudLayout {
+    /// (x, y, largeur, hauteur, taille de police)
+    pub base: Option<(f32, f32, f32, f32, Option<f32>)>,
+}
+
+impl Component for HudLayout {
+    type Storage = DenseVecStorage<Self>;
+}
+
+pub fn load_hud_scale() -> HudScale {
+    let path = match data_path("config/display.ron") {
+        Some(path) => path,
+        None => return HudScale::default(),
+    };
+
+    match DisplayConfig::load(&path) {
+        Ok(DisplayConfig {
+            dimensions: Some((width, height)),
+            ..
+        }) => HudScale {
+            reference: (width as f32, height as f32),
+            scale: 1.,
+        },
+        Ok(_) => HudScale::default(),
+        Err(e) => {
+            warn!("Failed to load display config {}: {}", path.display(), e);
+            HudScale::default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MenuStats {
+    pub shown: bool,
+    pub pressed: bool,
 }
 
 #[derive(Default)]
-pub struct Reading(pub bool);
+pub struct GraphicsMenu {
+    pub shown: bool,
+    pub selected: usize,
+}
+
+pub const INTRO_TEXT: &str =
+    "Vous devez rendre l'AFIT demain soir au plus tard, mais vous n'avez rien fait.\n\n\
+
+    Pris a la fois de panique et d'une enorme flemme, vous decidez de vous introduire la nuit\n \
+    en salle machine pour aller recuperer le travail des malheureux ayant oublie de fermer leur session.\n\n\
+
+    Mais attention, Bashar rode et il ne ne vous laissera pas faire !\n\
+    Alors si vous entendez un bruit etrange, cachez-vous vite sous une table,\n\
+    et vous ressortirez peut-etre vivant d'ici !\n\n\
+
+    Les pages d'AFIT qui trainent valent aussi des tests, marchez dessus pour les ramasser\n\
+    Appuyez sur 'F' pour allumer votre lampe, mais sa lumiere attire Bashar\n\
+    Appuyez sur 'Tab' pour voir vos statistiques, 'G' pour les graphismes\n\
+    Cliquez n'importe ou pour commencer";
+
+#[derive(Default)]
+pub struct Minimap {
+    pub root: Option<Entity>,
+    pub player: Option<Entity>,
+    pub computers: Vec<Entity>,
+    /// Rectangle du niveau represente par la minimap
+    pub bounds: Zone,
+    pub shown: bool,
+    pub pressed: bool,
+}
+
+// Pixels par unite du monde
+const MAP_SCALE: f32 = 8.0;
+
+pub const MAP_FLOOR_COLOR: [f32; 4] = [0.35, 0.35, 0.4, 0.8];
+pub const MAP_HIDE_COLOR: [f32; 4] = [0.15, 0.25, 0.5, 0.9];
+pub const MAP_LOCKED_COLOR: [f32; 4] = [0.2, 0.2, 0.2, 1.];
+pub const MAP_UNLOCKED_COLOR: [f32; 4] = [0.2, 0.9, 0.2, 1.];
+pub const MAP_PLAYER_COLOR: [f32; 4] = [1., 0.2, 0.2, 1.];
+
+/// Position (x, y) sur la minimap d'un point (x, z) du monde
+pub fn to_map(bounds: &Zone, x: f32, z: f32) -> (f32, f32) {
+    ((x - bounds.0) * MAP_SCALE, (bounds.3 - z) * MAP_SCALE)
+}
+
+pub const STAMINA_BAR_WIDTH: f32 = 200.;
+
+pub fn code_text(code_found: u8, objective: u8) -> String {
+    let passed = if objective == 0 {
+        1.0
+    } else {
+        code_found.min(objective) as f32 / objective as f32
+    };
+    format!("Tests passes a {}%", (passed * 100.0) as i32)
+}
+
+pub fn pages_text(pages: usize, total: usize) -> String {
+    format!("{}/{}", pages, total)
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    format!("{}m{:02}s", seconds / 60, seconds % 60)
+}
+
+pub fn objective_tracker_text(afit: &Afit, level: &Level) -> String {
+    let objective = &level.objective;
+    if afit.is_complete(objective) {
+        return "Fuyez par le couloir".to_string();
+    }
+
+    let mut lines = Vec::new();
+    if objective.codes > 0 {
+        lines.push(format!(
+            "Recuperer le code : {}/{}",
+            afit.code_found.min(objective.codes),
+            objective.codes
+        ));
+    }
+    if objective.pages > 0 {
+        lines.push(format!(
+            "Ramasser les pages : {}/{}",
+            afit.pages.len().min(objective.pages),
+            objective.pages
+        ));
+    }
+    lines.join("\n")
+}
+
+pub const LOG_LINES: usize = 4;
+
+/// Ligne du journal pour un evenement, s'il merite d'y figurer
+pub fn event_text(event: &GameEvent, level: &Level) -> Option<String> {
+    let text = match event {
+        GameEvent::ComputerUnlocked(computer_id) => {
+            let (x, _) = level.computer_position(*computer_id);
+            format!("Un ordinateur s'allume {}", level.side(x))
+        }
+        GameEvent::CodeCollected => "Vous recuperez un morceau de code".to_string(),
+        GameEvent::PageCollected => "Vous ramassez une page d'AFIT".to_string(),
+        GameEvent::BasharComing => "Vous entendez des pas...".to_string(),
+        GameEvent::BasharLeft => "Les pas s'eloignent".to_string(),
+        GameEvent::PlayerHid | GameEvent::PlayerCaught | GameEvent::GameWon => return None,
+    };
+    Some(text)
+}
+
+pub const SUBTITLE_LINES: usize = 3;
+
+/// Sous-titre d'un son du jeu
+pub fn caption_text(event: &GameEvent) -> Option<&'static str> {
+    match event {
+        GameEvent::ComputerUnlocked(_) => Some("[Un ordinateur demarre]"),
+        GameEvent::BasharComing => Some("[Des pas approchent]"),
+        GameEvent::PlayerCaught => Some("[Hurlement de Bashar]"),
+        _ => None,
+    }
+}
+
+pub fn stats_text(stats: &Stats) -> String {
+    format!(
+        "Statistiques\n\n\
+        Meilleur temps : {}\n\
+        Plus de code recupere : {}\n\
+        Morts : {}\n\
+        Cachettes : {}\n\
+        Survie moyenne : {}\n\n\
+        Appuyez sur 'Tab' pour revenir",
+        stats
+            .best_completion_time
+            .map_or("aucun".to_string(), format_duration),
+        stats.most_code_found,
+        stats.total_deaths,
+        stats.times_hidden,
+        stats
+            .average_survival_time()
+            .map_or("aucune".to_string(), format_duration),
+    )
+}
+
+pub const GRAPHICS_ENTRIES: usize = 6;
+
+pub fn graphics_text(settings: &Settings, selected: usize) -> String {
+    let yes_no = |value| if value { "oui" } else { "non" };
+    let entries: [String; GRAPHICS_ENTRIES] = [
+        format!("Plein ecran : {}", yes_no(settings.fullscreen)),
+        format!(
+            "Resolution : {}x{}",
+            settings.resolution.0, settings.resolution.1
+        ),
+        format!("Synchronisation verticale : {}", yes_no(settings.vsync)),
+        format!("Champ de vision : {}", settings.fov.round() as i32),
+        format!(
+            "Luminosite : {}%",
+            (settings.brightness * 100.).round() as i32
+        ),
+        format!("Sous-titres : {}", yes_no(settings.subtitles)),
+    ];
+
+    let mut text = "Graphismes\n\n".to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        text += if i == selected { "> " } else { "   " };
+        text += entry;
+        text += "\n";
+    }
+    text += "\nFleches pour choisir et modifier, 'G' pour revenir";
+    text
+}
+
+pub fn create_texts(
+    world: &mut World,
+    font: &FontHandle,
+    vignette: &UiImage,
+    page: &SpriteRender,
+) -> Texts {
+    let (code, pages, objective) = {
+        let afit = world.read_resource::<Afit>();
+        let level = world.read_resource::<Level>();
+        (
+            code_text(afit.code_found, level.objective.codes),
+            pages_text(afit.pages.len(), level.pages.len()),
+            objective_tracker_text(&afit, &level),
+        )
+    };
 
-pub fn create_texts(world: &mut World, font: &FontHandle) -> Texts {
     let hide = world
         .create_entity()
         .with(UiTransform::new(
@@ -37,6 +304,7 @@ pub fn create_texts(world: &mut World, font: &FontHandle) -> Texts {
             [1., 1., 1., 1.],
             40.,
         ))
+        .with(HudLayout::default())
         .build();
 
     let _use = world
@@ -57,6 +325,7 @@ pub fn create_texts(world: &mut World, font: &FontHandle) -> Texts {
             [1., 1., 1., 1.],
             40.,
         ))
+        .with(HudLayout::default())
         .build();
 
     let code = world
@@ -71,76 +340,559 @@ pub fn create_texts(world: &mut World, font: &FontHandle) -> Texts {
             500.,
             50.,
         ))
-        .with(UiText::new(
-            font.clone(),
-            "Tests passes a 0%".to_string(),
-            [1., 1., 1., 1.],
-            60.,
+        .with(UiText::new(font.clone(), code, [1., 1., 1., 1.], 60.))
+        .with(HudLayout::default())
+        .build();
+
+    // Progression des pages d'AFIT, sous le pourcentage de tests
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            "pages_icon".to_string(),
+            Anchor::TopLeft,
+            Anchor::TopLeft,
+            10.,
+            -110.,
+            1.,
+            64.,
+            36.,
         ))
+        .with(UiImage::Sprite(page.clone()))
+        .with(HudLayout::default())
         .build();
 
-    let mut text = UiText::new(
-        font.clone(),
-        "Vous devez rendre l'AFIT demain soir au plus tard, mais vous n'avez rien fait.\n\n\
+    let pages = world
+        .create_entity()
+        .with(UiTransform::new(
+            "pages".to_string(),
+            Anchor::TopLeft,
+            Anchor::TopLeft,
+            84.,
+            -110.,
+            1.,
+            200.,
+            40.,
+        ))
+        .with(UiText::new(font.clone(), pages, [1., 1., 1., 1.], 40.))
+        .with(HudLayout::default())
+        .build();
 
-        Pris a la fois de panique et d'une enorme flemme, vous decidez de vous introduire la nuit\n \
-        en salle machine pour aller recuperer le travail des malheureux ayant oublie de fermer leur session.\n\n\
+    let mut text = UiText::new(font.clone(), objective, [1., 1., 1., 1.], 35.);
+    text.line_mode = LineMode::Wrap;
+    text.align = Anchor::TopLeft;
 
-        Mais attention, Bashar rode et il ne ne vous laissera pas faire !\n\
-        Alors si vous entendez un bruit etrange, cachez-vous vite sous une table,\n\
-        et vous ressortirez peut-etre vivant d'ici !\n\n\
+    let objective = world
+        .create_entity()
+        .with(UiTransform::new(
+            "objective".to_string(),
+            Anchor::TopLeft,
+            Anchor::TopLeft,
+            10.,
+            -160.,
+            1.,
+            600.,
+            80.,
+        ))
+        .with(text)
+        .with(HudLayout::default())
+        .build();
 
-        Cliquez n'importe ou pour commencer"
-            .to_string(),
-        [1., 1., 1., 1.],
-        35.,
-    );
+    // Le journal part du bas, au dessus de la barre d'endurance
+    let log = (0..LOG_LINES)
+        .map(|i| {
+            let mut text = UiText::new(font.clone(), String::new(), [1., 1., 1., 0.], 30.);
+            text.align = Anchor::MiddleLeft;
+
+            world
+                .create_entity()
+                .with(UiTransform::new(
+                    format!("log_{}", i),
+                    Anchor::BottomLeft,
+                    Anchor::BottomLeft,
+                    50.,
+                    60. + i as f32 * 40.,
+                    1.,
+                    700.,
+                    40.,
+                ))
+                .with(text)
+                .with(HudLayout::default())
+                .build()
+        })
+        .collect();
+
+    let mut text = UiText::new(font.clone(), INTRO_TEXT.to_string(), [1., 1., 1., 1.], 35.);
     text.line_mode = LineMode::Wrap;
 
     let menu = world
+        .create_entity()
+        .with(
+            UiTransform::new(
+                "menu".to_string(),
+                Anchor::Middle,
+                Anchor::Middle,
+                0.,
+                0.,
+                1.,
+                0.,
+                425.,
+            )
+            .with_stretch(Stretch::X { x_margin: 50. }),
+        )
+        .with(text)
+        .with(HudLayout::default())
+        .build();
+
+    let stamina = world
         .create_entity()
         .with(UiTransform::new(
-            "menu".to_string(),
-            Anchor::Middle,
-            Anchor::Middle,
-            0.,
-            0.,
+            "stamina".to_string(),
+            Anchor::BottomLeft,
+            Anchor::BottomLeft,
+            50.,
+            30.,
             1.,
-            1500.,
-            375.,
+            STAMINA_BAR_WIDTH,
+            6.,
+        ))
+        .with(UiImage::SolidColor([1., 1., 1., 0.]))
+        .with(HudLayout::default())
+        .build();
+
+    let vignette = world
+        .create_entity()
+        .with(
+            UiTransform::new(
+                "vignette".to_string(),
+                Anchor::Middle,
+                Anchor::Middle,
+                0.,
+                0.,
+                0.5,
+                0.,
+                0.,
+            )
+            .with_stretch(Stretch::XY {
+                x_margin: 0.,
+                y_margin: 0.,
+                keep_aspect_ratio: false,
+            }),
+        )
+        .with(vignette.clone())
+        .with(Tint(Srgba::new(1., 1., 1., 0.)))
+        .with(HudLayout::default())
+        .build();
+
+    let mut text = UiText::new(font.clone(), String::new(), [1., 1., 1., 1.], 35.);
+    text.line_mode = LineMode::Wrap;
+    text.align = Anchor::BottomMiddle;
+
+    let subtitles = world
+        .create_entity()
+        .with(UiTransform::new(
+            "subtitles".to_string(),
+            Anchor::BottomMiddle,
+            Anchor::BottomMiddle,
+            0.,
+            160.,
+            // Par dessus Bashar, pour sous-titrer son cri
+            11.,
+            800.,
+            SUBTITLE_LINES as f32 * 40.,
         ))
         .with(text)
+        .with(HudLayout::default())
         .build();
 
+    let mut flash = |id: &str, anchor| {
+        world
+            .create_entity()
+            .with(
+                UiTransform::new(id.to_string(), anchor, anchor, 0., 0., 0.6, 30., 0.)
+                    .with_stretch(Stretch::Y { y_margin: 0. }),
+            )
+            .with(UiImage::SolidColor([1., 1., 1., 0.]))
+            .build()
+    };
+    let flash_left = flash("flash_left", Anchor::MiddleLeft);
+    let flash_right = flash("flash_right", Anchor::MiddleRight);
+
     Texts {
         hide: Some(hide),
         _use: Some(_use),
         code: Some(code),
+        pages: Some(pages),
+        objective: Some(objective),
+        log,
         menu: Some(menu),
+        stamina: Some(stamina),
+        vignette: Some(vignette),
+        subtitles: Some(subtitles),
+        flash_left: Some(flash_left),
+        flash_right: Some(flash_right),
     }
 }
 
+fn create_map_rect(
+    world: &mut World,
+    root: Entity,
+    bounds: &Zone,
+    id: String,
+    zone: &Zone,
+    color: [f32; 4],
+) {
+    let (x, y) = to_map(bounds, zone.0, zone.3);
+
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            id,
+            Anchor::BottomLeft,
+            Anchor::BottomLeft,
+            x,
+            y,
+            1.,
+            (zone.2 - zone.0) * MAP_SCALE,
+            (zone.3 - zone.1) * MAP_SCALE,
+        ))
+        .with(UiImage::SolidColor(color))
+        .with(Parent::new(root))
+        .with(HudLayout::default())
+        .build();
+}
+
+fn create_map_marker(
+    world: &mut World,
+    root: Entity,
+    bounds: &Zone,
+    id: String,
+    (x, z): (f32, f32),
+    size: f32,
+    color: [f32; 4],
+) -> Entity {
+    let (x, y) = to_map(bounds, x, z);
+
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            id,
+            Anchor::BottomLeft,
+            Anchor::Middle,
+            x,
+            y,
+            2.,
+            size,
+            size,
+        ))
+        .with(UiImage::SolidColor(color))
+        .with(Parent::new(root))
+        .with(HudLayout::default())
+        .build()
+}
+
+pub fn create_minimap(world: &mut World, level: &Level) -> Minimap {
+    let bounds = level.bounds();
+
+    let root = world
+        .create_entity()
+        .with(UiTransform::new(
+            "minimap".to_string(),
+            Anchor::TopRight,
+            Anchor::TopRight,
+            -20.,
+            -20.,
+            1.,
+            (bounds.2 - bounds.0) * MAP_SCALE,
+            (bounds.3 - bounds.1) * MAP_SCALE,
+        ))
+        .with(UiImage::SolidColor([0., 0., 0., 0.6]))
+        .with(HiddenPropagate::new())
+        .with(HudLayout::default())
+        .build();
+
+    for (i, zone) in level.corridors.iter().chain(&level.rooms).enumerate() {
+        create_map_rect(
+            world,
+            root,
+            &bounds,
+            format!("minimap_floor_{}", i),
+            zone,
+            MAP_FLOOR_COLOR,
+        );
+    }
+    for (i, zone) in level.hide_zones.iter().enumerate() {
+        create_map_rect(
+            world,
+            root,
+            &bounds,
+            format!("minimap_hide_{}", i),
+            zone,
+            MAP_HIDE_COLOR,
+        );
+    }
+
+    let computers = (0..level.computer_number())
+        .map(|computer_id| {
+            let (x, z) = level.computer_position(computer_id);
+            create_map_marker(
+                world,
+                root,
+                &bounds,
+                format!("minimap_computer_{}", computer_id),
+                (x - 0.175, z - 0.9),
+                5.,
+                MAP_LOCKED_COLOR,
+            )
+        })
+        .collect();
+
+    let player = create_map_marker(
+        world,
+        root,
+        &bounds,
+        "minimap_player".to_string(),
+        level.start,
+        7.,
+        MAP_PLAYER_COLOR,
+    );
+
+    Minimap {
+        root: Some(root),
+        player: Some(player),
+        computers,
+        bounds,
+        shown: false,
+        pressed: false,
+    }
+}
+
+pub const LOADING_BAR_WIDTH: f32 = 400.;
+
+pub struct LoadingScreen {
+    pub background: Entity,
+    pub bar: Entity,
+    pub text: Entity,
+}
+
+impl LoadingScreen {
+    pub fn delete(&self, world: &mut World) {
+        world
+            .delete_entities(&[self.bar, self.background, self.text])
+            .expect("Failed to delete the loading screen");
+    }
+}
+
+/// Police du systeme, disponible avant que nos propres assets soient charges
+fn default_font(world: &World) -> FontHandle {
+    get_default_font(
+        &world.read_resource::<Loader>(),
+        &world.read_resource::<AssetStorage<FontAsset>>(),
+    )
+}
+
+pub fn create_loading_screen(world: &mut World) -> LoadingScreen {
+    let font = default_font(world);
+
+    let background = world
+        .create_entity()
+        .with(UiTransform::new(
+            "loading_background".to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.,
+            -30.,
+            1.,
+            LOADING_BAR_WIDTH,
+            12.,
+        ))
+        .with(UiImage::SolidColor([0.1, 0.1, 0.12, 0.8]))
+        .build();
+
+    let bar = world
+        .create_entity()
+        .with(UiTransform::new(
+            "loading_bar".to_string(),
+            Anchor::MiddleLeft,
+            Anchor::MiddleLeft,
+            0.,
+            0.,
+            1.,
+            0.,
+            12.,
+        ))
+        .with(UiImage::SolidColor([1., 1., 1., 0.9]))
+        .with(Parent::new(background))
+        .build();
+
+    let text = world
+        .create_entity()
+        .with(UiTransform::new(
+            "loading_text".to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.,
+            10.,
+            1.,
+            LOADING_BAR_WIDTH,
+            40.,
+        ))
+        .with(UiText::new(
+            font,
+            "Chargement...".to_string(),
+            [1., 1., 1., 1.],
+            30.,
+        ))
+        .build();
+
+    LoadingScreen {
+        background,
+        bar,
+        text,
+    }
+}
+
+/// Resume de ce qu'il faut recuperer pour finir un niveau
+pub fn objective_text(objective: &Objective) -> String {
+    match (objective.codes, objective.pages) {
+        (codes, 0) => format!("{} tests", codes),
+        (0, pages) => format!("{} pages", pages),
+        (codes, pages) => format!("{} tests, {} pages", codes, pages),
+    }
+}
+
+pub fn level_select_text(
+    levels: &Levels,
+    selected: usize,
+    unlocked: usize,
+    finished: Option<usize>,
+    saved: Option<usize>,
+) -> String {
+    let mut text = String::new();
+    if let Some(level) = finished.and_then(|finished| levels.0.get(finished)) {
+        text += &format!("Niveau termine : {} !\n\n", level.name);
+    }
+
+    text += "Choix du niveau\n\n";
+    for (i, level) in levels.0.iter().enumerate() {
+        text += if i == selected { "> " } else { "   " };
+        text += &format!("{}. {}", i + 1, level.name);
+        if i > unlocked {
+            text += " (verrouille)";
+        } else {
+            text += &format!(" ({})", objective_text(&level.objective));
+        }
+        if saved == Some(i) {
+            text += " - sauvegarde";
+        }
+        text += "\n";
+    }
+    text += "\nFleches pour choisir, 'Entree' pour jouer, 'Echap' pour quitter";
+    text
+}
+
+pub fn create_level_select(world: &mut World, font: FontHandle, text: String) -> Entity {
+    let mut text = UiText::new(font, text, [1., 1., 1., 1.], 35.);
+    text.line_mode = LineMode::Wrap;
+
+    world
+        .create_entity()
+        .with(
+            UiTransform::new(
+                "level_select".to_string(),
+                Anchor::Middle,
+                Anchor::Middle,
+                0.,
+                0.,
+                1.,
+                0.,
+                0.,
+            )
+            .with_stretch(Stretch::XY {
+                x_margin: 50.,
+                y_margin: 50.,
+                keep_aspect_ratio: false,
+            }),
+        )
+        .with(text)
+        .build()
+}
+
+pub fn create_error_text(world: &mut World, message: String) -> Entity {
+    let font = default_font(world);
+
+    let mut text = UiText::new(font, message, [1., 1., 1., 1.], 24.);
+    text.line_mode = LineMode::Wrap;
+
+    world
+        .create_entity()
+        .with(
+            UiTransform::new(
+                "error".to_string(),
+                Anchor::Middle,
+                Anchor::Middle,
+                0.,
+                0.,
+                1.,
+                0.,
+                0.,
+            )
+            .with_stretch(Stretch::XY {
+                x_margin: 50.,
+                y_margin: 50.,
+                keep_aspect_ratio: false,
+            }),
+        )
+        .with(text)
+        .build()
+}
+
 #[derive(Debug, SystemDesc)]
 #[system_desc(name(TextSystemDesc))]
 pub struct TextSystem;
 
+impl PhaseSystem for TextSystem {
+    const PHASES: &'static [GamePhase] = &[GamePhase::Intro];
+}
+
 impl<'s> System<'s> for TextSystem {
     type SystemData = (
-        Write<'s, Reading>,
+        Write<'s, MenuStats>,
+        Read<'s, GraphicsMenu>,
+        Read<'s, Stats>,
         WriteStorage<'s, UiText>,
         Read<'s, Texts>,
         Read<'s, InputHandler<StringBindings>>,
     );
 
-    fn run(&mut self, (mut reading, mut ui, texts, inputs): Self::SystemData) {
-        if reading.0 && inputs.mouse_button_is_down(MouseButton::Left) {
-            reading.0 = false;
+    fn run(
+        &mut self,
+        (mut menu_stats, graphics_menu, stats, mut ui, texts, inputs): Self::SystemData,
+    ) {
+        // Pas de partie en cours (chargement, choix du niveau)
+        if texts.menu.is_none() {
+            return;
+        }
+
+        if let Some(pressed) = inputs.action_is_down("stats") {
+            // Les graphismes se quittent avec 'G'
+            if pressed && !menu_stats.pressed && !graphics_menu.shown {
+                menu_stats.pressed = true;
+                menu_stats.shown = !menu_stats.shown;
 
-            if let Some(menu) = texts.menu {
-                if let Some(text) = ui.get_mut(menu) {
-                    text.text = String::new();
+                if let Some(menu) = texts.menu {
+                    if let Some(text) = ui.get_mut(menu) {
+                        text.text = if menu_stats.shown {
+                            stats_text(&stats)
+                        } else {
+                            INTRO_TEXT.to_string()
+                        };
+                    }
                 }
             }
+
+            if !pressed && menu_stats.pressed {
+                menu_stats.pressed = false;
+            }
         }
     }
 }